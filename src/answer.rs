@@ -0,0 +1,94 @@
+use std::fmt::Display;
+
+/// What a part returns. Most answers are counts or sums, a few are negative, and some are text like `6,5,7,0` or a
+/// coordinate.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Unsigned(u64),
+    Signed(i64),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Unsigned(value) => write!(f, "{value}"),
+            Answer::Signed(value) => write!(f, "{value}"),
+            Answer::Text(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+macro_rules! from_unsigned {
+    ($($ty:ty),*) => {
+        $(impl From<$ty> for Answer {
+            fn from(value: $ty) -> Self {
+                Answer::Unsigned(value as u64)
+            }
+        })*
+    };
+}
+
+macro_rules! from_signed {
+    ($($ty:ty),*) => {
+        $(impl From<$ty> for Answer {
+            fn from(value: $ty) -> Self {
+                Answer::Signed(value as i64)
+            }
+        })*
+    };
+}
+
+from_unsigned!(u8, u16, u32, u64, usize);
+from_signed!(i8, i16, i32, i64, isize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+/// Compare against a stored answer, numbers by value so `-0` or `+5` still match.
+impl PartialEq<str> for Answer {
+    fn eq(&self, other: &str) -> bool {
+        let other = other.trim();
+
+        match self {
+            Answer::Unsigned(value) => other.parse::<i128>().is_ok_and(|other| other == *value as i128),
+            Answer::Signed(value) => other.parse::<i128>().is_ok_and(|other| other == *value as i128),
+            Answer::Text(value) => value == other,
+        }
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_and_compare() {
+        assert_eq!(Answer::from(3_714_264_u64).to_string(), "3714264");
+        assert_eq!(Answer::from(-12).to_string(), "-12");
+        assert_eq!(Answer::from("6,5,7,0").to_string(), "6,5,7,0");
+
+        assert_eq!(Answer::from(2usize), "2");
+        assert_eq!(Answer::from(-5), "-5");
+        assert_eq!(Answer::from(5u8), "+5");
+        assert_eq!(Answer::from("abc"), " abc\n");
+        assert_ne!(Answer::from(5u64), "6");
+        assert_ne!(Answer::from(5u64), "five");
+        assert_ne!(Answer::from("5,6"), "5, 6");
+    }
+}
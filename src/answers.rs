@@ -0,0 +1,130 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    examples::{ExpectedFile, Value},
+    submit::Ledger,
+    Part,
+};
+
+/// Answers for your own puzzle inputs, kept as `answers.toml` in the input directory.
+///
+/// Days are tables keyed by year then day, e.g. `[2024.01]` followed by `part_one = 11`.
+#[derive(Debug, Default)]
+pub struct Answers {
+    path: PathBuf,
+    answers: BTreeMap<(u64, u64, Part), String>,
+}
+
+impl Answers {
+    /// Open the answers at `path`, a missing file has no answers in it yet.
+    pub fn open(path: impl AsRef<Path>) -> crate::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self { path, ..Default::default() }),
+            Err(e) => return Err(e.into()),
+        };
+
+        let years: BTreeMap<String, BTreeMap<String, ExpectedFile>> = toml::from_str(&contents)?;
+        let mut answers = BTreeMap::new();
+
+        for (year, days) in years {
+            let year = year.parse().map_err(|_| eyre::eyre!("{}: `{year}` isn't a year", path.display()))?;
+
+            for (day, expected) in days {
+                let day = day.parse().map_err(|_| eyre::eyre!("{}: `{day}` isn't a day", path.display()))?;
+
+                for (part, value) in [(Part::One, expected.part_one), (Part::Two, expected.part_two)] {
+                    if let Some(value) = value {
+                        answers.insert((year, day, part), value.into());
+                    }
+                }
+            }
+        }
+
+        Ok(Self { path, answers })
+    }
+
+    pub fn get(&self, year: u64, day: u64, part: Part) -> Option<&str> {
+        self.answers.get(&(year, day, part)).map(String::as_str)
+    }
+
+    /// Remember an accepted answer, rewriting the file.
+    pub fn record(&mut self, year: u64, day: u64, part: Part, answer: &str) -> crate::Result<()> {
+        self.answers.insert((year, day, part), answer.to_string());
+
+        let mut years: BTreeMap<String, BTreeMap<String, ExpectedFile>> = BTreeMap::new();
+        for ((year, day, part), answer) in &self.answers {
+            let expected = years.entry(year.to_string()).or_default().entry(format!("{day:0>2}")).or_default();
+            match part {
+                Part::One => expected.part_one = Some(Value::from(answer.as_str())),
+                Part::Two => expected.part_two = Some(Value::from(answer.as_str())),
+            }
+        }
+
+        crate::write_atomic(&self.path, &toml::to_string(&years)?)
+    }
+}
+
+/// Stars earned for a day, a part counts once its answer is known or the ledger says it was accepted.
+pub fn stars(answers: &Answers, ledger: &Ledger, year: u64, day: u64) -> usize {
+    Part::ALL.iter().filter(|&&part| answers.get(year, day, part).is_some() || ledger.solved(year, day, part)).count()
+}
+
+/// Draw a year's calendar, five days to a row, with a `*` for each star.
+pub fn calendar(year: u64, stars: impl Fn(u64) -> usize) -> String {
+    let days = crate::calendar::days_in(year);
+    let total = (1..=days).map(&stars).sum::<usize>();
+    let mut out = format!("{year}: {total}/{} stars\n", days * 2);
+
+    for week in (1..=days).collect::<Vec<_>>().chunks(5) {
+        let row = week.iter().map(|&day| format!("{day:>2} {:<2}", "*".repeat(stars(day)))).collect::<Vec<_>>();
+        let _ = writeln!(out, "{}", row.join("  ").trim_end());
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::temp_dir;
+
+    #[test]
+    fn open_and_record() {
+        let path = temp_dir("answers").join("answers.toml");
+        assert!(Answers::open(&path).unwrap().get(2024, 1, Part::One).is_none());
+
+        std::fs::write(&path, "[2024.01]\npart_one = 11\npart_two = 31\n\n[2024.25]\npart_one = \"abc\"\n").unwrap();
+        let mut answers = Answers::open(&path).unwrap();
+
+        assert_eq!(answers.get(2024, 1, Part::One), Some("11"));
+        assert_eq!(answers.get(2024, 1, Part::Two), Some("31"));
+        assert_eq!(answers.get(2024, 25, Part::One), Some("abc"));
+        assert_eq!(answers.get(2024, 25, Part::Two), None);
+
+        answers.record(2024, 9, Part::One, "1928").unwrap();
+        let answers = Answers::open(&path).unwrap();
+        assert_eq!(answers.get(2024, 9, Part::One), Some("1928"));
+        assert_eq!(answers.get(2024, 25, Part::One), Some("abc"));
+        assert!(std::fs::read_to_string(&path).unwrap().contains("[2024.09]\npart_one = 1928\n"));
+    }
+
+    #[test]
+    fn draw_calendar() {
+        let drawn = calendar(2025, |day| match day {
+            1 | 2 => 2,
+            3 => 1,
+            _ => 0,
+        });
+
+        assert_eq!(
+            drawn,
+            "2025: 5/24 stars\n 1 **   2 **   3 *    4      5\n 6      7      8      9     10\n11     12\n"
+        );
+    }
+}
@@ -1,8 +1,264 @@
+use std::path::{Path, PathBuf};
+
+use aoc::{
+    answers::Answers,
+    calendar::{self, SystemClock},
+    config::Config,
+    report::Format,
+    selection::Selection,
+    submit::Verdict,
+    Part,
+};
+use clap::{Parser, Subcommand};
+use eyre::OptionExt;
+
+/// Advent of Code solutions.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Run solutions, e.g. `aoc run 2024 6 --part 2`. Runs everything when no command is given.
+    Run {
+        /// Year(s) to run: `2024`, `2015-2024`, `2023,2024` or `all`.
+        #[arg(default_value = "all")]
+        year: Selection,
+
+        /// Day(s) to run: `6`, `1..=5`, `1-5`, `1,3,5` or `all`.
+        #[arg(default_value = "all")]
+        day: Selection,
+
+        /// Only run this part (1 or 2), otherwise both are run.
+        #[arg(short, long)]
+        part: Option<Part>,
+
+        /// Read the input from a file, or `-` for stdin, instead of the cached input. Needs a single day.
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+
+        /// Print results as a `table`, or as `json` or `csv` records. Logs always go to stderr.
+        #[arg(short, long, default_value = "table")]
+        format: Format,
+    },
+
+    /// Draw the star calendar for a year, e.g. `aoc stars 2024`. Defaults to the latest year.
+    Stars { year: Option<u64> },
+
+    /// Solve a single part and submit its answer, e.g. `aoc submit 2024 7 2`.
+    Submit { year: u64, day: u64, part: Part },
+
+    /// Print a puzzle's description as Markdown, e.g. `aoc read 2024 5`.
+    Read {
+        year: u64,
+        day: u64,
+
+        /// Fetch the description again, even if it's already cached.
+        #[arg(long)]
+        refresh: bool,
+    },
+
+    /// Fetch a puzzle's input and description, e.g. `aoc fetch 2024 5`.
+    Fetch {
+        year: Option<u64>,
+        day: Option<u64>,
+
+        /// Sleep until the puzzle unlocks first. Without a year and day, waits for the next puzzle.
+        #[arg(long)]
+        wait: bool,
+    },
+
+    /// Generate a new day from the built-in template, e.g. `aoc new 2024 9`. Run from the repository root.
+    New {
+        year: u64,
+        day: u64,
+
+        /// Also fetch the input, the description and its examples.
+        #[arg(long)]
+        fetch: bool,
+    },
+}
+
+/// Read an input given on the command line, `-` being stdin.
+fn read_input(path: &Path) -> aoc::Result<String> {
+    if path == Path::new("-") {
+        return Ok(std::io::read_to_string(std::io::stdin())?);
+    }
+
+    std::fs::read_to_string(path).map_err(|e| eyre::eyre!("unable to read {}: {e}", path.display()))
+}
+
+fn run(year: &Selection, day: &Selection, part: Option<Part>, input: Option<&Path>, format: Format) -> aoc::Result<()> {
+    year.ensure_within(calendar::FIRST_YEAR..=calendar::latest_year(&SystemClock), "year")?;
+    day.ensure_within(1..=25, "day")?;
+
+    let parts = match part {
+        Some(part) => vec![part],
+        None => Part::ALL.to_vec(),
+    };
+
+    let years = aoc::solution::years();
+
+    for missing in year.missing(&years) {
+        tracing::warn!("skipping {missing}, there are no solutions for it");
+    }
+
+    let mut solvers = Vec::new();
+
+    for year in year.resolve(&years) {
+        let days = aoc::solution::days(year);
+
+        for missing in day.missing(&days) {
+            tracing::warn!("skipping y{year}d{missing:0>2}, it hasn't been solved yet");
+        }
+
+        // SAFE: `days` only lists registered solutions
+        solvers.extend(day.resolve(&days).into_iter().map(|day| aoc::solution::find(year, day).unwrap()));
+    }
+
+    eyre::ensure!(!solvers.is_empty(), "nothing to run, no solved days match the selection");
+
+    let config = Config::from_env()?;
+    let mut runs = Vec::new();
+
+    match input {
+        Some(path) => {
+            eyre::ensure!(solvers.len() == 1, "--input needs a single year and day, but {} matched", solvers.len());
+
+            // answers only apply to your own input
+            runs.push(solvers[0].run(&read_input(path)?, &parts, None)?);
+        }
+        None => {
+            let answers = Answers::open(config.answers_path())?;
+
+            for solver in &solvers {
+                runs.push(solver.run(&aoc::get_input(solver.year(), solver.day())?, &parts, Some(&answers))?);
+            }
+        }
+    }
+
+    print!("{}", aoc::report::render(&runs, format, config.part_budget)?);
+
+    if let Some(budget) = config.part_budget {
+        for label in runs.iter().flat_map(|run| run.over_budget(budget)) {
+            tracing::warn!("{label} took longer than its {budget:?} budget");
+        }
+    }
+
+    if format == Format::Table && input.is_none() {
+        let mut years = solvers.iter().map(|solver| solver.year()).collect::<Vec<_>>();
+        years.dedup();
+        for year in years {
+            stars(Some(year))?;
+        }
+    }
+
+    let wrong = runs.iter().flat_map(|run| &run.parts).filter(|part| part.verified() == Some(false)).count();
+    eyre::ensure!(wrong == 0, "{wrong} answer(s) didn't match {}", config.answers_path().display());
+
+    Ok(())
+}
+
+fn stars(year: Option<u64>) -> aoc::Result<()> {
+    let year = year.unwrap_or_else(|| calendar::latest_year(&SystemClock));
+    calendar::ensure_valid(year, 1)?;
+
+    let config = Config::from_env()?;
+    let answers = Answers::open(config.answers_path())?;
+    let ledger = aoc::submit::Ledger::open(config.ledger_path())?;
+
+    print!("{}", aoc::answers::calendar(year, |day| aoc::answers::stars(&answers, &ledger, year, day)));
+    Ok(())
+}
+
+fn submit(year: u64, day: u64, part: Part) -> aoc::Result<()> {
+    let solver = aoc::solution::find(year, day).ok_or_eyre("there's no solution for this day")?;
+    let input = solver.parse(&aoc::get_input(year, day)?)?;
+    let answer = solver.solve(input.as_ref(), part)?.to_string();
+
+    tracing::info!("submitting {answer} for {}", aoc::solution::label(year, day, part));
+
+    let config = Config::from_env()?;
+    let client = aoc::client::Client::new(&config)?;
+    let mut ledger = aoc::submit::Ledger::open(config.ledger_path())?;
+
+    match aoc::submit::submit(&client, &mut ledger, year, day, part, &answer)? {
+        Verdict::Correct => {
+            tracing::info!("that's the right answer!");
+            Answers::open(config.answers_path())?.record(year, day, part, &answer)?;
+        }
+        Verdict::Wrong => tracing::warn!("that's not the right answer"),
+        Verdict::TooHigh => tracing::warn!("that's not the right answer, it's too high"),
+        Verdict::TooLow => tracing::warn!("that's not the right answer, it's too low"),
+        Verdict::RateLimited(wait) => tracing::warn!("answered too recently, wait {}s", wait.as_secs()),
+        Verdict::AlreadySolved => tracing::warn!("this part was already solved"),
+    }
+
+    Ok(())
+}
+
+fn fetch(year: Option<u64>, day: Option<u64>, wait: bool) -> aoc::Result<()> {
+    let (year, day) = match (year, day, wait) {
+        (Some(year), Some(day), _) => (year, day),
+        (None, None, true) => calendar::next_unlock(&SystemClock),
+        _ => eyre::bail!("give both a year and a day, or neither with --wait"),
+    };
+
+    if wait {
+        calendar::wait_for(&SystemClock, year, day)?;
+    }
+
+    aoc::get_input(year, day)?;
+    aoc::puzzle::get_puzzle(&Config::from_env()?, year, day, false)?;
+
+    tracing::info!("fetched y{year}d{day:0>2}");
+    Ok(())
+}
+
+fn new_day(year: u64, day: u64, fetch: bool) -> aoc::Result<()> {
+    calendar::ensure_valid(year, day)?;
+
+    let root = Path::new(".");
+    eyre::ensure!(
+        root.join("Cargo.toml").is_file() && root.join("src").join("lib.rs").is_file(),
+        "run this from the root of the repository"
+    );
+
+    let written = aoc::scaffold::new_day(root, year, day)?;
+    for path in &written {
+        tracing::info!("wrote {}", path.display());
+    }
+
+    if let Err(e) = aoc::scaffold::format(&written) {
+        tracing::warn!("{e}");
+    }
+
+    if fetch {
+        aoc::get_input(year, day)?;
+        aoc::puzzle::get_puzzle(&Config::from_env()?, year, day, false)?;
+    }
+
+    Ok(())
+}
+
 fn main() -> aoc::Result<()> {
     dotenvy::dotenv().ok();
-    tracing_subscriber::fmt::init();
+    tracing_subscriber::fmt().with_writer(std::io::stderr).init();
 
-    aoc::y2024::main()?;
+    match Cli::parse().command {
+        Some(Command::Run { year, day, part, input, format }) => run(&year, &day, part, input.as_deref(), format)?,
+        Some(Command::Stars { year }) => stars(year)?,
+        Some(Command::Submit { year, day, part }) => submit(year, day, part)?,
+        Some(Command::Read { year, day, refresh }) => {
+            println!("{}", aoc::puzzle::get_puzzle(&Config::from_env()?, year, day, refresh)?)
+        }
+        Some(Command::Fetch { year, day, wait }) => fetch(year, day, wait)?,
+        Some(Command::New { year, day, fetch }) => new_day(year, day, fetch)?,
+        None => run(&Selection::All, &Selection::All, None, None, Format::Table)?,
+    }
 
     Ok(())
 }
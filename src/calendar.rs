@@ -0,0 +1,199 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The first year of Advent of Code.
+pub const FIRST_YEAR: u64 = 2015;
+
+/// Puzzles unlock at midnight US Eastern, which is always UTC-5 in December.
+const UNLOCK_OFFSET: Duration = Duration::from_secs(5 * 60 * 60);
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Where the time comes from, so tests can pretend it's December.
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+    fn sleep(&self, duration: Duration);
+}
+
+/// The real clock.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration)
+    }
+}
+
+/// How many puzzles a year has, from 2025 onwards there are only twelve.
+pub fn days_in(year: u64) -> u64 {
+    if year >= 2025 {
+        12
+    } else {
+        25
+    }
+}
+
+/// Days since the unix epoch for a date in the proleptic Gregorian calendar.
+fn days_from_civil(year: u64, month: u64, day: u64) -> u64 {
+    // see http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The year of a point in time, as seen in US Eastern.
+fn year_of(time: SystemTime) -> u64 {
+    let eastern = time.duration_since(UNIX_EPOCH).unwrap_or_default().saturating_sub(UNLOCK_OFFSET);
+    let days = eastern.as_secs() / DAY.as_secs();
+
+    // find the first year starting after this day, it's at most one year past the naive guess
+    let mut year = 1970 + days / 366;
+    while days_from_civil(year + 1, 1, 1) <= days {
+        year += 1;
+    }
+
+    year
+}
+
+/// When a puzzle unlocks.
+pub fn unlock_time(year: u64, day: u64) -> SystemTime {
+    UNIX_EPOCH + DAY * days_from_civil(year, 12, day) as u32 + UNLOCK_OFFSET
+}
+
+/// The most recent year with at least one unlocked puzzle.
+pub fn latest_year(clock: &dyn Clock) -> u64 {
+    let year = year_of(clock.now());
+    if clock.now() >= unlock_time(year, 1) {
+        year
+    } else {
+        year - 1
+    }
+}
+
+/// Ensure a puzzle exists in the calendar, regardless of whether it's unlocked yet.
+pub fn ensure_valid(year: u64, day: u64) -> crate::Result<()> {
+    eyre::ensure!(year >= FIRST_YEAR, "aoc started in {FIRST_YEAR}, there's no {year}");
+    eyre::ensure!((1..=days_in(year)).contains(&day), "aoc {year} days must be between 01 and {:0>2}", days_in(year));
+
+    Ok(())
+}
+
+pub fn is_unlocked(clock: &dyn Clock, year: u64, day: u64) -> bool {
+    clock.now() >= unlock_time(year, day)
+}
+
+/// Render a duration as e.g. `3h 2m 5s`.
+pub fn humanize(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (days, hours, minutes, seconds) = (secs / 86_400, secs / 3_600 % 24, secs / 60 % 60, secs % 60);
+
+    match (days, hours, minutes) {
+        (0, 0, 0) => format!("{seconds}s"),
+        (0, 0, _) => format!("{minutes}m {seconds}s"),
+        (0, _, _) => format!("{hours}h {minutes}m {seconds}s"),
+        _ => format!("{days}d {hours}h {minutes}m"),
+    }
+}
+
+/// Ensure a puzzle exists and has unlocked.
+pub fn ensure_unlocked(clock: &dyn Clock, year: u64, day: u64) -> crate::Result<()> {
+    ensure_valid(year, day)?;
+
+    let unlock = unlock_time(year, day);
+    if let Ok(remaining) = unlock.duration_since(clock.now()) {
+        if !remaining.is_zero() {
+            eyre::bail!("y{year}d{day:0>2} hasn't unlocked yet, it unlocks in {}", humanize(remaining));
+        }
+    }
+
+    Ok(())
+}
+
+/// The next puzzle to unlock.
+pub fn next_unlock(clock: &dyn Clock) -> (u64, u64) {
+    let now = clock.now();
+    let year = year_of(now);
+
+    (1..=days_in(year)).find(|&day| unlock_time(year, day) > now).map(|day| (year, day)).unwrap_or((year + 1, 1))
+}
+
+/// Sleep until a puzzle unlocks, waking up now and then to say how long is left.
+pub fn wait_for(clock: &dyn Clock, year: u64, day: u64) -> crate::Result<()> {
+    ensure_valid(year, day)?;
+
+    let unlock = unlock_time(year, day);
+
+    while let Ok(remaining) = unlock.duration_since(clock.now()) {
+        if remaining.is_zero() {
+            break;
+        }
+
+        tracing::info!("y{year}d{day:0>2} unlocks in {}", humanize(remaining));
+        clock.sleep(remaining.min(Duration::from_secs(15 * 60)));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::FakeClock;
+
+    // 2024-12-05T04:59:59Z, a second before day 5 unlocks
+    const BEFORE_Y2024D05: u64 = 1_733_374_799;
+
+    #[test]
+    fn unlock_times() {
+        assert_eq!(unlock_time(2015, 1), UNIX_EPOCH + Duration::from_secs(1_448_946_000));
+        assert_eq!(unlock_time(2024, 5), UNIX_EPOCH + Duration::from_secs(BEFORE_Y2024D05 + 1));
+    }
+
+    #[test]
+    fn validation() {
+        let clock = FakeClock::at(BEFORE_Y2024D05);
+
+        assert!(ensure_unlocked(&clock, 2024, 4).is_ok());
+        assert!(ensure_unlocked(&clock, 2024, 5).is_err());
+        assert!(ensure_unlocked(&clock, 2014, 1).is_err());
+        assert!(ensure_valid(2024, 25).is_ok());
+        assert!(ensure_valid(2025, 12).is_ok());
+        assert!(ensure_valid(2025, 13).is_err());
+
+        clock.sleep(Duration::from_secs(1));
+        assert!(ensure_unlocked(&clock, 2024, 5).is_ok());
+    }
+
+    #[test]
+    fn next_and_latest() {
+        let clock = FakeClock::at(BEFORE_Y2024D05);
+        assert_eq!(next_unlock(&clock), (2024, 5));
+        assert_eq!(latest_year(&clock), 2024);
+
+        // 2025-06-01T00:00:00Z, nothing from 2025 has unlocked
+        let clock = FakeClock::at(1_748_736_000);
+        assert_eq!(next_unlock(&clock), (2025, 1));
+        assert_eq!(latest_year(&clock), 2024);
+
+        // 2025-12-31T12:00:00Z, every 2025 puzzle is out
+        let clock = FakeClock::at(1_767_182_400);
+        assert_eq!(next_unlock(&clock), (2026, 1));
+        assert_eq!(latest_year(&clock), 2025);
+    }
+
+    #[test]
+    fn wait_until_unlocked() {
+        let clock = FakeClock::at(BEFORE_Y2024D05 - 3 * 60 * 60);
+        wait_for(&clock, 2024, 5).unwrap();
+
+        assert!(is_unlocked(&clock, 2024, 5));
+        assert_eq!(clock.now(), unlock_time(2024, 5));
+    }
+}
@@ -0,0 +1,91 @@
+use std::{
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use eyre::OptionExt;
+
+use crate::config::Config;
+
+/// When the last request went out, shared by every client so the whole process is throttled.
+static LAST_REQUEST: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// A client for adventofcode.com, authenticated with the `session` cookie.
+pub struct Client {
+    base_url: String,
+    min_interval: Duration,
+    http: reqwest::blocking::Client,
+}
+
+impl Client {
+    pub fn new(config: &Config) -> crate::Result<Self> {
+        let token = config.token.as_deref().ok_or_eyre("AOC_TOKEN isn't set, see the README")?;
+
+        // build a cookie jar
+        let jar = Arc::new(reqwest::cookie::Jar::default());
+        jar.add_cookie_str(&format!("session={token}"), &reqwest::Url::from_str(&config.base_url)?);
+
+        let http =
+            reqwest::blocking::ClientBuilder::new().cookie_provider(jar).user_agent(&config.user_agent).build()?;
+
+        Ok(Self {
+            base_url: config.base_url.trim_end_matches('/').to_string(),
+            min_interval: config.min_interval,
+            http,
+        })
+    }
+
+    /// Wait until at least `min_interval` has passed since the previous request.
+    fn throttle(&self) {
+        let mut last = LAST_REQUEST.lock().unwrap_or_else(|e| e.into_inner());
+
+        if let Some(remaining) = last.and_then(|last| self.min_interval.checked_sub(last.elapsed())) {
+            std::thread::sleep(remaining);
+        }
+
+        *last = Some(Instant::now());
+    }
+
+    /// Make a GET request, returning the status alongside the body whatever it is.
+    pub fn get_raw(&self, path: &str) -> crate::Result<(reqwest::StatusCode, String)> {
+        self.throttle();
+        let response = self.http.get(format!("{}{path}", self.base_url)).send()?;
+        Ok((response.status(), response.text()?))
+    }
+
+    pub fn get(&self, path: &str) -> crate::Result<String> {
+        self.throttle();
+        Ok(self.http.get(format!("{}{path}", self.base_url)).send()?.error_for_status()?.text()?)
+    }
+
+    pub fn post_form(&self, path: &str, form: &[(&str, &str)]) -> crate::Result<String> {
+        self.throttle();
+        Ok(self.http.post(format!("{}{path}", self.base_url)).form(form).send()?.error_for_status()?.text()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::StandIn;
+
+    #[test]
+    fn throttled_with_user_agent() {
+        let server = StandIn::serve(vec![(200, "one"), (200, "two")]);
+        let config = Config {
+            base_url: server.url.clone(),
+            user_agent: "aoc-tests (someone@example.com)".to_string(),
+            min_interval: Duration::from_millis(200),
+            ..server.config()
+        };
+        let client = Client::new(&config).unwrap();
+
+        let started = Instant::now();
+        assert_eq!(client.get("/one").unwrap(), "one");
+        assert_eq!(client.get("/two").unwrap(), "two");
+        assert!(started.elapsed() >= Duration::from_millis(200));
+
+        assert_eq!(server.requests()[1].header("user-agent"), Some("aoc-tests (someone@example.com)"));
+    }
+}
@@ -0,0 +1,97 @@
+use std::{path::PathBuf, time::Duration};
+
+/// Sent with every request, AoC asks for a way to contact whoever runs the tool.
+pub const DEFAULT_USER_AGENT: &str = concat!("aoc/", env!("CARGO_PKG_VERSION"), " (+https://github.com/narigama/aoc)");
+
+/// Settings for talking to adventofcode.com and caching what comes back, read from the environment (and `.env`).
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// `AOC_BASE_URL`, point this at a local stand-in to avoid the real site.
+    pub base_url: String,
+    /// `AOC_TOKEN`, the `session` cookie.
+    pub token: Option<String>,
+    /// `AOC_USER_AGENT`, ideally including your email address.
+    pub user_agent: String,
+    /// `AOC_MIN_INTERVAL_MS`, the least time left between two requests.
+    pub min_interval: Duration,
+    /// `AOC_PART_BUDGET_MS`, parts taking longer than this are flagged by the runner.
+    pub part_budget: Option<Duration>,
+    /// `AOC_INPUT_DIR`, where inputs, their answers and the submission ledger are kept.
+    pub input_dir: PathBuf,
+    /// `AOC_PUZZLE_DIR`, where puzzle descriptions are kept.
+    pub puzzle_dir: PathBuf,
+    /// `AOC_EXAMPLES_DIR`, where the examples pulled out of puzzle descriptions are kept. They're shared with
+    /// everyone, unlike inputs, so they're committed.
+    pub examples_dir: PathBuf,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            base_url: crate::BASE_URL.to_string(),
+            token: None,
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            min_interval: Duration::from_secs(1),
+            part_budget: None,
+            input_dir: PathBuf::from("input"),
+            puzzle_dir: PathBuf::from("puzzle"),
+            examples_dir: PathBuf::from("examples"),
+        }
+    }
+}
+
+impl Config {
+    pub fn from_env() -> crate::Result<Self> {
+        let mut config = Self::default();
+
+        if let Ok(base_url) = std::env::var("AOC_BASE_URL") {
+            config.base_url = base_url;
+        }
+
+        config.token = std::env::var("AOC_TOKEN").ok();
+
+        if let Ok(user_agent) = std::env::var("AOC_USER_AGENT") {
+            config.user_agent = user_agent;
+        }
+
+        if let Ok(interval) = std::env::var("AOC_MIN_INTERVAL_MS") {
+            let millis = interval.parse().map_err(|_| eyre::eyre!("AOC_MIN_INTERVAL_MS must be a number"))?;
+            config.min_interval = Duration::from_millis(millis);
+        }
+
+        if let Ok(budget) = std::env::var("AOC_PART_BUDGET_MS") {
+            let millis = budget.parse().map_err(|_| eyre::eyre!("AOC_PART_BUDGET_MS must be a number"))?;
+            config.part_budget = Some(Duration::from_millis(millis));
+        }
+
+        if let Ok(input_dir) = std::env::var("AOC_INPUT_DIR") {
+            config.input_dir = PathBuf::from(input_dir);
+        }
+
+        if let Ok(puzzle_dir) = std::env::var("AOC_PUZZLE_DIR") {
+            config.puzzle_dir = PathBuf::from(puzzle_dir);
+        }
+
+        if let Ok(examples_dir) = std::env::var("AOC_EXAMPLES_DIR") {
+            config.examples_dir = PathBuf::from(examples_dir);
+        }
+
+        Ok(config)
+    }
+
+    pub fn input_path(&self, year: u64, day: u64) -> PathBuf {
+        self.input_dir.join(format!("{year}")).join(format!("{day:0>2}.txt"))
+    }
+
+    pub fn answers_path(&self) -> PathBuf {
+        self.input_dir.join("answers.toml")
+    }
+
+    pub fn ledger_path(&self) -> PathBuf {
+        self.input_dir.join("submissions.tsv")
+    }
+
+    pub fn puzzle_path(&self, year: u64, day: u64) -> PathBuf {
+        self.puzzle_dir.join(format!("{year}")).join(format!("{day:0>2}.md"))
+    }
+}
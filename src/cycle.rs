@@ -0,0 +1,112 @@
+//! Finding where a state machine starts repeating itself, so a billion steps can be skipped.
+
+use std::{collections::HashMap, hash::Hash};
+
+/// Where a sequence of states starts to repeat: state `start + length` is state `start` again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Period {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Period {
+    /// The earliest step whose state is the same as the state after `n` steps.
+    pub fn earliest(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// Step from `initial` until a state repeats, keeping every state seen, and where they start repeating.
+///
+/// `step` returns `None` when the machine halts instead, and so there's no cycle.
+pub fn trace<S: Clone + Eq + Hash>(initial: S, mut step: impl FnMut(&S) -> Option<S>) -> (Vec<S>, Option<Period>) {
+    let mut seen = HashMap::from([(initial.clone(), 0)]);
+    let mut states = vec![initial];
+
+    while let Some(next) = step(&states[states.len() - 1]) {
+        if let Some(&start) = seen.get(&next) {
+            return (states, Some(Period { start, length: seen.len() - start }));
+        }
+
+        seen.insert(next.clone(), states.len());
+        states.push(next);
+    }
+
+    (states, None)
+}
+
+/// Where the states from `initial` start to repeat, or `None` if `step` halts first by returning `None`.
+///
+/// Uses Brent's algorithm, so nothing is stored or hashed, at the cost of stepping through the states a few times.
+pub fn find_cycle<S: Clone + Eq>(initial: S, mut step: impl FnMut(&S) -> Option<S>) -> Option<Period> {
+    // find the length by moving the tortoise up to the hare at every power of two
+    let (mut power, mut length) = (1, 1);
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial)?;
+
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare)?;
+        length += 1;
+    }
+
+    // then the start, by walking two states `length` apart until they meet
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = step(&hare)?;
+    }
+
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = step(&hare)?;
+        start += 1;
+    }
+
+    Some(Period { start, length })
+}
+
+/// The state after `n` steps from `initial`, only stepping until the states repeat.
+pub fn state_after<S: Clone + Eq + Hash>(initial: S, mut step: impl FnMut(&S) -> S, n: usize) -> S {
+    let mut remaining = n;
+    let (mut states, period) = trace(initial, |state| {
+        // stop early if we get there before a cycle shows up
+        remaining = remaining.checked_sub(1)?;
+        Some(step(state))
+    });
+
+    match period {
+        Some(period) => states.swap_remove(period.earliest(n)),
+        // SAFE: there's always at least the initial state
+        None => states.pop().unwrap(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cycles() {
+        // 1, 2, 4, 8, 16, 12, 4, ... starts repeating at step 2 every 4 steps
+        let step = |&n: &u64| n * 2 % 20;
+
+        assert_eq!(find_cycle(1, |n| Some(step(n))), Some(Period { start: 2, length: 4 }));
+        assert_eq!(find_cycle(1, |&n| (n < 8).then(|| step(&n))), None);
+        assert_eq!(find_cycle(0, |&n| Some((n + 1) % 5)), Some(Period { start: 0, length: 5 }));
+
+        assert_eq!(state_after(1, step, 0), 1);
+        assert_eq!(state_after(1, step, 4), 16);
+        assert_eq!(state_after(1, step, 6), 4);
+        assert_eq!(state_after(1, step, 1_000_000_001), 12);
+    }
+}
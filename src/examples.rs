@@ -0,0 +1,243 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{config::Config, puzzle, Part, Solver};
+
+/// An example input from a puzzle description, with the answers the description gives for it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Example {
+    pub input: String,
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+impl Example {
+    pub fn expected(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part_one.as_deref(),
+            Part::Two => self.part_two.as_deref(),
+        }
+    }
+}
+
+/// Answers are written as integers where they fit, anything else is kept as a string.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum Value {
+    Integer(i64),
+    Text(String),
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        value.parse().map(Value::Integer).unwrap_or_else(|_| Value::Text(value.to_string()))
+    }
+}
+
+impl From<Value> for String {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Integer(value) => value.to_string(),
+            Value::Text(value) => value,
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct ExpectedFile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part_one: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part_two: Option<Value>,
+}
+
+fn example_path(config: &Config, year: u64, day: u64, n: usize, extension: &str) -> PathBuf {
+    config.examples_dir.join(format!("{year}")).join(format!("{day:0>2}-{n}.{extension}"))
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut in_tag = false;
+
+    for ch in html.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            ch if !in_tag => out.push(ch),
+            _ => {}
+        }
+    }
+
+    puzzle::decode_entities(&out)
+}
+
+/// Contents of every `open ... close` block in `html`, in order.
+fn blocks<'a>(html: &'a str, open: &str, close: &str) -> Vec<&'a str> {
+    let mut blocks = Vec::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find(open) {
+        rest = &rest[start + open.len()..];
+        let Some(end) = rest.find(close) else { break };
+        blocks.push(&rest[..end]);
+        rest = &rest[end + close.len()..];
+    }
+
+    blocks
+}
+
+/// Pull the `<pre><code>` examples out of a puzzle page.
+///
+/// The last highlighted `<code><em>` in each part is taken to be the answer for the most recent example, which is
+/// how the descriptions tend to be written. Check the result, it's a guess.
+pub fn extract(html: &str) -> Vec<Example> {
+    let mut examples: Vec<Example> = Vec::new();
+
+    for (index, article) in puzzle::articles(html).into_iter().enumerate() {
+        for block in blocks(article, "<pre><code>", "</code></pre>") {
+            examples.push(Example { input: strip_tags(block), ..Default::default() });
+        }
+
+        let answer = blocks(article, "<code><em>", "</em></code>").last().map(|answer| strip_tags(answer));
+
+        if let (Some(example), Some(answer)) = (examples.last_mut(), answer) {
+            match index {
+                0 => example.part_one = Some(answer),
+                _ => example.part_two = Some(answer),
+            }
+        }
+    }
+
+    examples
+}
+
+/// Load every stored example for a day, in order.
+pub fn load(config: &Config, year: u64, day: u64) -> crate::Result<Vec<Example>> {
+    let mut examples = Vec::new();
+
+    for n in 1.. {
+        let Ok(input) = std::fs::read_to_string(example_path(config, year, day, n, "txt")) else { break };
+
+        let expected = match std::fs::read_to_string(example_path(config, year, day, n, "toml")) {
+            Ok(expected) => toml::from_str(&expected)?,
+            Err(_) => ExpectedFile::default(),
+        };
+
+        examples.push(Example {
+            input,
+            part_one: expected.part_one.map(String::from),
+            part_two: expected.part_two.map(String::from),
+        });
+    }
+
+    Ok(examples)
+}
+
+/// Store examples next to each other as `{day}-{n}.txt` and `{day}-{n}.toml`.
+///
+/// Existing inputs and answers are kept, so hand corrections survive, only answers that are still missing are
+/// filled in. This is how part two's answers arrive once it unlocks.
+pub fn save(config: &Config, year: u64, day: u64, examples: &[Example]) -> crate::Result<()> {
+    let existing = load(config, year, day)?;
+
+    for (index, example) in examples.iter().enumerate() {
+        let n = index + 1;
+        let input_path = example_path(config, year, day, n, "txt");
+
+        if let Some(parent) = input_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let current = existing.get(index);
+        if current.is_none() {
+            std::fs::write(&input_path, &example.input)?;
+        }
+
+        let merged = Example {
+            input: current.map_or_else(|| example.input.clone(), |current| current.input.clone()),
+            part_one: current.and_then(|current| current.part_one.clone()).or_else(|| example.part_one.clone()),
+            part_two: current.and_then(|current| current.part_two.clone()).or_else(|| example.part_two.clone()),
+        };
+
+        if current != Some(&merged) {
+            let expected = ExpectedFile {
+                part_one: merged.part_one.as_deref().map(Value::from),
+                part_two: merged.part_two.as_deref().map(Value::from),
+            };
+            std::fs::write(example_path(config, year, day, n, "toml"), toml::to_string(&expected)?)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Run a solution against every stored example that has an answer for `part`, returning how many were checked.
+pub fn verify(solver: &dyn Solver, part: Part) -> crate::Result<usize> {
+    let config = Config::from_env()?;
+    let mut checked = 0;
+
+    for (index, example) in load(&config, solver.year(), solver.day())?.iter().enumerate() {
+        let Some(expected) = example.expected(part) else { continue };
+
+        let input = solver.parse(&example.input)?;
+        let answer = solver.solve(input.as_ref(), part)?;
+        let label = crate::solution::label(solver.year(), solver.day(), part);
+        eyre::ensure!(answer == *expected, "{label}: example {} gave `{answer}`, expected `{expected}`", index + 1);
+
+        checked += 1;
+    }
+
+    Ok(checked)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 3: Mull It Over ---</h2><p>For example, consider the following section of corrupted memory:</p>
+<pre><code>x<em>mul(2,4)</em>%&amp;mul[3,7]</code></pre>
+<p>Adding up the result of each instruction produces <code><em>161</em></code>.</p>
+</article>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<pre><code>xmul(2,4)&amp;mul[3,7]!^<em>don't()</em></code></pre>
+<p>This time, the sum of the results is <code><em>48</em></code>.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn extract_examples() {
+        assert_eq!(
+            extract(PAGE),
+            vec![
+                Example { input: "xmul(2,4)%&mul[3,7]".to_string(), part_one: Some("161".to_string()), part_two: None },
+                Example {
+                    input: "xmul(2,4)&mul[3,7]!^don't()".to_string(),
+                    part_one: None,
+                    part_two: Some("48".to_string())
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn save_and_merge() {
+        let config = Config { examples_dir: crate::testing::temp_dir("examples"), ..Default::default() };
+        let mut examples = extract(PAGE);
+
+        save(&config, 2024, 3, &examples[..1]).unwrap();
+        assert_eq!(load(&config, 2024, 3).unwrap(), examples[..1]);
+
+        // hand corrections survive, part two's examples and answers are added once it unlocks
+        let path = config.examples_dir.join("2024").join("03-1.toml");
+        std::fs::write(&path, "part_one = 160\n").unwrap();
+        examples[0].part_two = Some("48".to_string());
+        save(&config, 2024, 3, &examples).unwrap();
+
+        let loaded = load(&config, 2024, 3).unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!((loaded[0].part_one.as_deref(), loaded[0].part_two.as_deref()), (Some("160"), Some("48")));
+        assert_eq!(loaded[1], examples[1]);
+    }
+}
@@ -0,0 +1,220 @@
+use std::fmt::Display;
+
+use crate::{
+    parsing::ParseError,
+    point::{Direction, Point, Vec2},
+};
+
+/// A dense, row-major rectangle of cells, like most puzzle maps.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self { width, height, cells: vec![fill; width * height] }
+    }
+
+    /// Parse one row per line, mapping each character to a cell. Every row has to be the same width, a row that isn't
+    /// is a [`ParseError`] pointing where it stops matching.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> T) -> crate::Result<Self> {
+        let mut width = None;
+        let mut cells = Vec::new();
+        let mut height = 0;
+
+        for line in input.trim_end().lines() {
+            let before = cells.len();
+            cells.extend(line.chars().map(&mut cell));
+
+            let row = cells.len() - before;
+            let width = *width.get_or_insert(row);
+            if row != width {
+                // past the last cell that fits, or the end of a short line
+                let at = line.char_indices().nth(width).map_or(line.len(), |(index, _)| index);
+                let message = format!("the line is {row} wide, expected {width} like the lines before it");
+                return Err(ParseError::new(&line[at..], message).locate(input).into());
+            }
+
+            height += 1;
+        }
+
+        let width = width.unwrap_or_default();
+        eyre::ensure!(width > 0, "the grid is empty");
+
+        Ok(Self { width, height, cells })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        point.within(self.width, self.height)
+    }
+
+    fn index(&self, point: Point) -> Option<usize> {
+        self.contains(point).then(|| point.y as usize * self.width + point.x as usize)
+    }
+
+    /// The cell at `point`, or `None` when it's off the grid.
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.index(point).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.index(point).map(|index| &mut self.cells[index])
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Point> + '_ {
+        (0..self.height as i64).flat_map(|y| (0..self.width as i64).map(move |x| Point::new(x, y)))
+    }
+
+    /// Every position alongside its cell, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> + '_ {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The first position, row by row, holding `value`.
+    pub fn find(&self, value: &T) -> Option<Point>
+    where
+        T: PartialEq,
+    {
+        self.iter().find(|(_, cell)| *cell == value).map(|(point, _)| point)
+    }
+
+    /// Apply `f` to every cell, keeping the shape.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid { width: self.width, height: self.height, cells: self.cells.iter().map(f).collect() }
+    }
+
+    fn neighbours<'a>(&'a self, point: Point, directions: &'a [Direction]) -> impl Iterator<Item = Point> + 'a {
+        directions.iter().map(move |&direction| point.step(direction)).filter(|&point| self.contains(point))
+    }
+
+    /// The up to four positions sharing an edge with `point`, clockwise from above.
+    pub fn neighbours_4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours(point, &Direction::CARDINAL)
+    }
+
+    /// The up to eight positions around `point`, including diagonals, clockwise from above.
+    pub fn neighbours_8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours(point, &Direction::ALL)
+    }
+
+    /// Cells along a line from `start`, moving `step` each time, until it leaves the grid.
+    pub fn walk(&self, start: Point, step: Vec2) -> impl Iterator<Item = &T> + '_ {
+        std::iter::successors(Some(start), move |&point| Some(point + step)).map_while(|point| self.get(point))
+    }
+
+    /// Each row, top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        // not `chunks`, which can't make rows of width 0
+        (0..self.height).map(|y| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    /// Each column, left to right, from the top.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.width as i64).map(|x| self.walk(Point::new(x, 0), Direction::South.unit()))
+    }
+
+    /// Each diagonal running down and to the right, starting from the bottom left corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        let (width, height) = (self.width as i64, self.height as i64);
+        let starts = (0..height).rev().map(|y| Point::new(0, y)).chain((1..width).map(|x| Point::new(x, 0)));
+        starts.map(|start| self.walk(start, Direction::SouthEast.unit()))
+    }
+
+    /// Each diagonal running up and to the right, starting from the top left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        let (width, height) = (self.width as i64, self.height as i64);
+        let starts = (0..height).map(|y| Point::new(0, y)).chain((1..width).map(move |x| Point::new(x, height - 1)));
+        starts.map(|start| self.walk(start, Direction::NorthEast.unit()))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "ab.\n.^c\n";
+
+    fn line<'a>(cells: impl Iterator<Item = &'a char>) -> String {
+        cells.collect()
+    }
+
+    #[test]
+    fn parse_and_index() {
+        let grid = Grid::parse(INPUT, |ch| ch).unwrap();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(Point::new(1, 0)), Some(&'b'));
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(0, -1)), None);
+        assert_eq!(grid.find(&'^'), Some(Point::new(1, 1)));
+        assert_eq!(grid.to_string(), INPUT);
+
+        assert_eq!(
+            Grid::parse("ab\nc\n", |ch| ch).unwrap_err().to_string(),
+            "line 2, column 2: the line is 1 wide, expected 2 like the lines before it"
+        );
+        assert!(Grid::parse("ab\nabc\n", |ch| ch).unwrap_err().to_string().starts_with("line 2, column 3:"));
+        assert!(Grid::parse("", |ch| ch).is_err());
+
+        let mut walls = grid.map(|&ch| ch == '.');
+        *walls.get_mut(Point::ORIGIN).unwrap() = true;
+        assert_eq!(walls.iter().filter(|(_, &wall)| wall).count(), 3);
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = Grid::new(3, 2, 0);
+
+        let points = |points: &[(i64, i64)]| points.iter().map(|&point| Point::from(point)).collect::<Vec<_>>();
+
+        assert_eq!(grid.neighbours_4(Point::ORIGIN).collect::<Vec<_>>(), points(&[(1, 0), (0, 1)]));
+        assert_eq!(
+            grid.neighbours_8(Point::new(1, 0)).collect::<Vec<_>>(),
+            points(&[(2, 0), (2, 1), (1, 1), (0, 1), (0, 0)])
+        );
+        assert_eq!(grid.neighbours_8(Point::new(1, 1)).count(), 5);
+    }
+
+    #[test]
+    fn lines() {
+        let grid = Grid::parse(INPUT, |ch| ch).unwrap();
+
+        assert_eq!(grid.rows().map(|row| row.iter().collect::<String>()).collect::<Vec<_>>(), vec!["ab.", ".^c"]);
+        assert_eq!(grid.columns().map(line).collect::<Vec<_>>(), vec!["a.", "b^", ".c"]);
+        assert_eq!(grid.diagonals().map(line).collect::<Vec<_>>(), vec![".", "a^", "bc", "."]);
+        assert_eq!(grid.anti_diagonals().map(line).collect::<Vec<_>>(), vec!["a", ".b", "^.", "c"]);
+
+        let empty = Grid::new(0, 2, 'x');
+        assert_eq!(empty.rows().count(), 2);
+        assert_eq!(empty.columns().count(), 0);
+        assert_eq!(empty.to_string(), "\n\n");
+    }
+}
@@ -1,10 +1,33 @@
-pub mod util;
+pub mod answer;
+pub mod answers;
+pub mod calendar;
+pub mod client;
+pub mod config;
+pub mod cycle;
+pub mod examples;
+pub mod grid;
+pub mod math;
+pub mod order;
+pub mod parsing;
+pub mod point;
+pub mod puzzle;
+pub mod report;
+pub mod scaffold;
+pub mod search;
+pub mod selection;
+pub mod solution;
+pub mod submit;
+#[cfg(test)]
+mod testing;
 pub mod y2024;
 
-use std::{io::Write, str::FromStr, sync::Arc};
+use std::{io::Write, path::Path};
 
 use eyre::OptionExt;
 
+pub use answer::Answer;
+pub use solution::{Part, Solution, Solver};
+
 pub type Error = eyre::ErrReport;
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -16,37 +39,109 @@ pub struct Pair<T> {
 
 pub const BASE_URL: &str = "https://adventofcode.com";
 
-pub fn get_input(year: u64, day: u64) -> Result<String> {
+/// Write a file by way of a temporary file and a rename, so a failure never leaves half a file behind.
+pub fn write_atomic(path: &Path, contents: &str) -> Result<()> {
+    let parent = path.parent().ok_or_eyre("unable to get parent dir of target path")?;
+    let name = path.file_name().ok_or_eyre("target path has no file name")?.to_string_lossy();
+
+    // ensure the parent directory structure exists
+    std::fs::create_dir_all(parent)?;
+
+    let temp_path = parent.join(format!(".{name}.tmp"));
+    std::fs::File::create(&temp_path)?.write_all(contents.as_bytes())?;
+    std::fs::rename(&temp_path, path)?;
+
+    Ok(())
+}
+
+/// Fetch a puzzle input, refusing anything that looks like an error page rather than an input.
+pub fn fetch_input(client: &client::Client, year: u64, day: u64) -> Result<String> {
+    let (status, body) = client.get_raw(&format!("/{year}/day/{day}/input"))?;
+
+    if body.contains("Please log in to get your puzzle input") {
+        eyre::bail!("adventofcode.com wants a login, AOC_TOKEN is missing or has expired");
+    }
+    if body.contains("Please don't repeatedly request this endpoint before it unlocks") {
+        eyre::bail!("y{year}d{day:0>2} hasn't unlocked yet");
+    }
+    eyre::ensure!(status.is_success(), "adventofcode.com replied {status} for y{year}d{day:0>2}: {}", body.trim());
+
+    let start = body.trim_start().get(..64).unwrap_or(body.trim_start()).to_ascii_lowercase();
+    eyre::ensure!(
+        !start.starts_with("<!doctype") && !start.starts_with("<html"),
+        "adventofcode.com sent a web page instead of the y{year}d{day:0>2} input, is AOC_TOKEN still valid?"
+    );
+    eyre::ensure!(!body.trim().is_empty(), "adventofcode.com sent an empty input for y{year}d{day:0>2}");
+
+    Ok(body)
+}
+
+/// Read a puzzle input from the cache, fetching it first if it isn't there.
+pub fn get_input_with(config: &config::Config, clock: &dyn calendar::Clock, year: u64, day: u64) -> Result<String> {
     // validate input
-    eyre::ensure!((2015..=2024).contains(&year), "aoc years must be between 2015 and 2024");
-    eyre::ensure!((1..=25).contains(&day), "aoc days must be between 01 and 25");
+    calendar::ensure_unlocked(clock, year, day)?;
 
-    let input_path = std::path::PathBuf::new().join("input").join(format!("{year}")).join(format!("{day:0>2}.txt"));
+    let input_path = config.input_path(year, day);
     if !input_path.is_file() {
         tracing::info!("fetching y{year}d{day:0>2} from the internet");
 
-        // grab the AOC_TOKEN
-        let aoc_token = std::env::var("AOC_TOKEN")?;
+        // fetch the input from the site and cache it
+        let input = fetch_input(&client::Client::new(config)?, year, day)?;
+        write_atomic(&input_path, &input)?;
+    }
 
-        // build a cookie jar
-        let jar = Arc::new(reqwest::cookie::Jar::default());
-        jar.add_cookie_str(&format!("session={aoc_token}"), &reqwest::Url::from_str(BASE_URL)?);
+    Ok(std::fs::read_to_string(&input_path)?)
+}
 
-        // fetch the input from the site
-        let input = reqwest::blocking::ClientBuilder::new()
-            .cookie_provider(jar)
-            .build()?
-            .get(format!("{BASE_URL}/{year}/day/{day}/input"))
-            .send()?
-            .error_for_status()?
-            .text()?;
+pub fn get_input(year: u64, day: u64) -> Result<String> {
+    get_input_with(&config::Config::from_env()?, &calendar::SystemClock, year, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{temp_dir, StandIn};
 
-        // ensure the parent directory structure exists
-        std::fs::create_dir_all(input_path.parent().ok_or_eyre("unable to get parent dir of target path")?)?;
+    #[test]
+    fn refuse_error_pages() {
+        let server = StandIn::serve(vec![
+            (400, "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"),
+            (404, "Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is..."),
+            (200, "<!DOCTYPE html>\n<html lang=\"en-us\"><head><title>Advent of Code</title></head></html>"),
+            (500, "Internal Server Error"),
+            (200, "3   4\n4   3\n"),
+        ]);
+        let client = client::Client::new(&server.config()).unwrap();
 
-        // write the contents to the file
-        std::fs::File::create(&input_path)?.write_all(input.as_bytes())?;
+        assert!(fetch_input(&client, 2024, 1).unwrap_err().to_string().contains("AOC_TOKEN"));
+        assert!(fetch_input(&client, 2024, 1).unwrap_err().to_string().contains("hasn't unlocked"));
+        assert!(fetch_input(&client, 2024, 1).unwrap_err().to_string().contains("web page"));
+        assert!(fetch_input(&client, 2024, 1).unwrap_err().to_string().contains("500"));
+        assert_eq!(fetch_input(&client, 2024, 1).unwrap(), "3   4\n4   3\n");
     }
 
-    Ok(std::fs::read_to_string(&input_path)?)
+    #[test]
+    fn fetch_once_then_cache() {
+        let server = StandIn::serve(vec![(200, "3   4\n4   3\n")]);
+        let config = server.config();
+        let clock = crate::testing::FakeClock::at(1_733_029_200); // 2024-12-01T05:00:00Z
+
+        assert_eq!(get_input_with(&config, &clock, 2024, 1).unwrap(), "3   4\n4   3\n");
+        assert_eq!(get_input_with(&config, &clock, 2024, 1).unwrap(), "3   4\n4   3\n");
+        assert!(config.input_dir.join("2024").join("01.txt").is_file());
+        assert_eq!(server.requests().len(), 1);
+        assert_eq!(server.requests()[0].path, "/2024/day/1/input");
+
+        assert!(get_input_with(&config, &clock, 2024, 2).is_err(), "day 2 hasn't unlocked yet");
+    }
+
+    #[test]
+    fn atomic_writes() {
+        let path = temp_dir("atomic").join("2024").join("01.txt");
+        write_atomic(&path, "first").unwrap();
+        write_atomic(&path, "second").unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "second");
+        assert_eq!(std::fs::read_dir(path.parent().unwrap()).unwrap().count(), 1);
+    }
 }
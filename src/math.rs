@@ -0,0 +1,193 @@
+//! Number theory that keeps turning up in puzzles: cycles lining up, modular arithmetic and digit tricks.
+
+/// The greatest common divisor, `gcd(0, 0)` is 0.
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The least common multiple, `lcm(0, n)` is 0.
+pub fn lcm(a: u64, b: u64) -> u64 {
+    if a == 0 || b == 0 {
+        0
+    } else {
+        a / gcd(a, b) * b
+    }
+}
+
+/// `(g, x, y)` such that `a * x + b * y == g`, where `g` is the gcd of `a` and `b`.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (g, x, y) = wide_extended_gcd(a as i128, b as i128);
+    (g as i64, x as i64, y as i64)
+}
+
+/// [`extended_gcd`] with room for products of moduli.
+fn wide_extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// The `x` in `0..modulus` with `a * x ≡ 1`, `None` unless `a` and `modulus` are coprime and `modulus` is positive.
+pub fn mod_inverse(a: i64, modulus: i64) -> Option<i64> {
+    if modulus <= 0 {
+        return None;
+    }
+
+    let (g, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+    (g == 1).then(|| x.rem_euclid(modulus))
+}
+
+/// The smallest `x` with `x ≡ remainder` for every `(remainder, modulus)`, and the modulus it repeats with.
+///
+/// The moduli don't have to be coprime, `None` means the congruences contradict each other, a modulus isn't positive
+/// or the combined modulus doesn't fit in an `i64`.
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let (mut x, mut m) = (0i128, 1i128);
+
+    for &(remainder, modulus) in congruences {
+        if modulus <= 0 {
+            return None;
+        }
+
+        let (remainder, modulus) = (remainder as i128, modulus as i128);
+        let (g, p, _) = wide_extended_gcd(m, modulus);
+
+        let difference = remainder - x;
+        if difference % g != 0 {
+            return None;
+        }
+
+        // step x by multiples of m until it also fits the new modulus
+        let step = modulus / g;
+        let k = (difference / g % step * p).rem_euclid(step);
+        x += m * k;
+        m = m.checked_mul(step).filter(|&m| m <= i64::MAX as i128)?;
+        x = x.rem_euclid(m);
+    }
+
+    Some((i64::try_from(x).ok()?, i64::try_from(m).ok()?))
+}
+
+/// `base` to the power `exponent`, modulo `modulus`, without overflowing along the way. `None` for a modulus of 0.
+pub fn mod_pow(base: u64, mut exponent: u64, modulus: u64) -> Option<u64> {
+    if modulus == 0 {
+        return None;
+    }
+
+    let modulus = modulus as u128;
+    let mut base = base as u128 % modulus;
+    let mut result = 1 % modulus;
+
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exponent >>= 1;
+    }
+
+    Some(result as u64)
+}
+
+/// How many decimal digits `n` is written with, 0 has one.
+pub fn digits(n: u64) -> u32 {
+    n.checked_ilog10().map_or(1, |log| log + 1)
+}
+
+/// The digits of `a` followed by the digits of `b`, so `concat(12, 345)` is 12345.
+pub fn concat(a: u64, b: u64) -> u64 {
+    a * 10u64.pow(digits(b)) + b
+}
+
+/// Like [`concat`], `None` if the result doesn't fit.
+pub fn checked_concat(a: u64, b: u64) -> Option<u64> {
+    a.checked_mul(10u64.checked_pow(digits(b))?)?.checked_add(b)
+}
+
+/// The number `n` starts with if it ends in the digits of `suffix`, so `strip_suffix(156, 6)` is 15.
+///
+/// This undoes [`concat`], `strip_suffix(6, 6)` is 0.
+pub fn strip_suffix(n: u64, suffix: u64) -> Option<u64> {
+    match 10u64.checked_pow(digits(suffix)) {
+        Some(power) => (n % power == suffix).then_some(n / power),
+        // the suffix is as long as a u64 gets
+        None => (n == suffix).then_some(0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn divisors() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+
+        assert_eq!(extended_gcd(240, 46), (2, -9, 47));
+        assert_eq!(extended_gcd(-4, 6).0, 2);
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(4, 8), None);
+        assert_eq!(mod_inverse(3, 0), None);
+        assert_eq!(mod_inverse(3, -11), None);
+    }
+
+    #[test]
+    fn congruences() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(2, 3), (0, 0)]), None);
+        assert_eq!(crt(&[(1, -4)]), None);
+        assert_eq!(crt(&[(1, 1 << 31), (2, (1 << 31) - 1)]), Some((2_147_483_649, 4_611_686_016_279_904_256)));
+        // the combined modulus is past i64::MAX
+        assert_eq!(crt(&[(1, 1 << 32), (2, (1 << 32) - 1)]), None);
+
+        assert_eq!(mod_pow(4, 13, 497), Some(445));
+        assert_eq!(mod_pow(u64::MAX, 2, u64::MAX - 1), Some(1));
+        assert_eq!(mod_pow(5, 0, 1), Some(0));
+        assert_eq!(mod_pow(5, 3, 0), None);
+    }
+
+    #[test]
+    fn digit_helpers() {
+        assert_eq!(digits(0), 1);
+        assert_eq!(digits(9), 1);
+        assert_eq!(digits(10), 2);
+        assert_eq!(digits(u64::MAX), 20);
+
+        assert_eq!(concat(12, 345), 12345);
+        assert_eq!(concat(15, 6), 156);
+        assert_eq!(concat(1, 0), 10);
+        assert_eq!(checked_concat(12, 345), Some(12345));
+        assert_eq!(checked_concat(u64::MAX / 10, 99), None);
+
+        assert_eq!(strip_suffix(156, 6), Some(15));
+        assert_eq!(strip_suffix(156, 56), Some(1));
+        assert_eq!(strip_suffix(156, 156), Some(0));
+        assert_eq!(strip_suffix(156, 5), None);
+        assert_eq!(strip_suffix(10, 0), Some(1));
+        assert_eq!(strip_suffix(u64::MAX, u64::MAX), Some(0));
+    }
+}
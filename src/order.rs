@@ -0,0 +1,202 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet},
+    fmt::{Debug, Display},
+    hash::Hash,
+};
+
+/// A set of "`before` comes before `after`" rules, not every pair has to be ordered.
+#[derive(Debug, Clone)]
+pub struct PartialOrder<T> {
+    after: HashMap<T, HashSet<T>>,
+}
+
+impl<T> Default for PartialOrder<T> {
+    fn default() -> Self {
+        Self { after: HashMap::new() }
+    }
+}
+
+/// Rules that contradict each other, listed so the last one leads back to the first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<T>(pub Vec<T>);
+
+impl<T: Display> Display for Cycle<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "the rules contain a cycle: ")?;
+        for node in &self.0 {
+            write!(f, "{node} -> ")?;
+        }
+        match self.0.first() {
+            Some(first) => write!(f, "{first}"),
+            None => write!(f, "?"),
+        }
+    }
+}
+
+impl<T: Debug + Display> std::error::Error for Cycle<T> {}
+
+impl<T: Clone + Eq + Hash> PartialOrder<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, before: T, after: T) {
+        self.after.entry(before).or_default().insert(after);
+    }
+
+    /// Whether there's a rule putting `before` ahead of `after`.
+    pub fn has_rule(&self, before: &T, after: &T) -> bool {
+        self.after.get(before).is_some_and(|after_set| after_set.contains(after))
+    }
+
+    /// Indices of the nodes that must come after `nodes[index]`, ignoring anything not in `nodes`.
+    fn successors<'a>(
+        &'a self,
+        nodes: &'a [T],
+        positions: &'a HashMap<&T, usize>,
+        index: usize,
+    ) -> impl Iterator<Item = usize> + 'a {
+        self.after.get(&nodes[index]).into_iter().flatten().filter_map(|after| positions.get(after).copied())
+    }
+
+    /// The first rule `sequence` breaks as `(before, after)`, or `None` if it's consistent.
+    ///
+    /// Each element's rules are checked once, so it's linear in the sequence and the rules that touch it.
+    pub fn first_violation(&self, sequence: &[T]) -> Option<(T, T)> {
+        let positions = sequence.iter().enumerate().map(|(index, node)| (node, index)).collect::<HashMap<_, _>>();
+
+        (0..sequence.len()).find_map(|index| {
+            self.successors(sequence, &positions, index)
+                .find(|&after| after < index)
+                .map(|after| (sequence[index].clone(), sequence[after].clone()))
+        })
+    }
+
+    pub fn is_consistent(&self, sequence: &[T]) -> bool {
+        self.first_violation(sequence).is_none()
+    }
+
+    /// A cycle among the rules between `nodes`, if there is one.
+    pub fn find_cycle(&self, nodes: &[T]) -> Option<Cycle<T>> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum Mark {
+            New,
+            Open,
+            Done,
+        }
+
+        fn visit<T: Clone + Eq + Hash>(
+            order: &PartialOrder<T>,
+            nodes: &[T],
+            positions: &HashMap<&T, usize>,
+            marks: &mut [Mark],
+            stack: &mut Vec<usize>,
+            index: usize,
+        ) -> Option<Cycle<T>> {
+            marks[index] = Mark::Open;
+            stack.push(index);
+
+            for next in order.successors(nodes, positions, index) {
+                match marks[next] {
+                    Mark::Open => {
+                        let start = stack.iter().position(|&open| open == next)?;
+                        return Some(Cycle(stack[start..].iter().map(|&open| nodes[open].clone()).collect()));
+                    }
+                    Mark::New => {
+                        if let Some(cycle) = visit(order, nodes, positions, marks, stack, next) {
+                            return Some(cycle);
+                        }
+                    }
+                    Mark::Done => {}
+                }
+            }
+
+            stack.pop();
+            marks[index] = Mark::Done;
+            None
+        }
+
+        let positions = nodes.iter().enumerate().map(|(index, node)| (node, index)).collect::<HashMap<_, _>>();
+        let mut marks = vec![Mark::New; nodes.len()];
+
+        (0..nodes.len()).find_map(|index| {
+            (marks[index] == Mark::New)
+                .then(|| visit(self, nodes, &positions, &mut marks, &mut Vec::new(), index))
+                .flatten()
+        })
+    }
+
+    /// Sort `nodes` so every rule between them holds, only rules between them count.
+    ///
+    /// Nodes the rules don't order keep their original order. Fails with the cycle if the rules contradict each
+    /// other.
+    pub fn sort(&self, nodes: &[T]) -> Result<Vec<T>, Cycle<T>> {
+        let positions = nodes.iter().enumerate().map(|(index, node)| (node, index)).collect::<HashMap<_, _>>();
+
+        let mut incoming = vec![0; nodes.len()];
+        for index in 0..nodes.len() {
+            for after in self.successors(nodes, &positions, index) {
+                incoming[after] += 1;
+            }
+        }
+
+        // Kahn's algorithm, always taking the earliest ready node
+        let mut ready = (0..nodes.len()).filter(|&index| incoming[index] == 0).map(Reverse).collect::<BinaryHeap<_>>();
+        let mut sorted = Vec::with_capacity(nodes.len());
+
+        while let Some(Reverse(index)) = ready.pop() {
+            sorted.push(nodes[index].clone());
+
+            for after in self.successors(nodes, &positions, index) {
+                incoming[after] -= 1;
+                if incoming[after] == 0 {
+                    ready.push(Reverse(after));
+                }
+            }
+        }
+
+        if sorted.len() < nodes.len() {
+            return Err(self.find_cycle(nodes).unwrap_or_else(|| Cycle(nodes.to_vec())));
+        }
+
+        Ok(sorted)
+    }
+}
+
+impl<T: Clone + Eq + Hash> FromIterator<(T, T)> for PartialOrder<T> {
+    fn from_iter<I: IntoIterator<Item = (T, T)>>(rules: I) -> Self {
+        let mut order = Self::new();
+        for (before, after) in rules {
+            order.add(before, after);
+        }
+        order
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sort_subsets() {
+        let order = [(1, 2), (2, 3), (4, 3), (9, 1)].into_iter().collect::<PartialOrder<_>>();
+
+        assert_eq!(order.sort(&[3, 2, 1]), Ok(vec![1, 2, 3]));
+        assert_eq!(order.sort(&[3, 5, 4]), Ok(vec![5, 4, 3]));
+        assert_eq!(order.sort(&[3, 1]), Ok(vec![3, 1]), "1 < 3 only holds through 2");
+
+        assert!(order.is_consistent(&[9, 1, 2, 4, 3]));
+        assert_eq!(order.first_violation(&[1, 3, 2]), Some((2, 3)));
+        assert_eq!(order.first_violation(&[4, 1, 9]), Some((9, 1)));
+    }
+
+    #[test]
+    fn report_cycles() {
+        let order = [(1, 2), (2, 3), (3, 1), (3, 4)].into_iter().collect::<PartialOrder<_>>();
+
+        assert_eq!(order.find_cycle(&[4, 1, 2]), None);
+        assert_eq!(order.find_cycle(&[4, 1, 2, 3]), Some(Cycle(vec![1, 2, 3])));
+        assert_eq!(order.sort(&[3, 2, 1]).unwrap_err().to_string(), "the rules contain a cycle: 3 -> 1 -> 2 -> 3");
+    }
+}
@@ -0,0 +1,181 @@
+//! Helpers for the usual shapes of puzzle input: numbers scattered through lines, one value per line, and sections
+//! split by blank lines.
+//!
+//! Errors point at the text they're about. Every helper only ever hands out slices of the input it was given, so
+//! [`ParseError::locate`] can turn that back into a line and column, and [`crate::Solver`] does so for the whole
+//! input whenever a day's `parse` fails.
+
+use std::{fmt::Display, str::FromStr};
+
+/// Something in the input that couldn't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    message: String,
+    /// Where the offending text lives in memory, until it's found in an input.
+    address: usize,
+    position: Option<(usize, usize)>,
+}
+
+impl ParseError {
+    /// An error about `text`, which should be a slice of the input so it can be located.
+    pub fn new(text: &str, message: impl Display) -> Self {
+        Self { message: message.to_string(), address: text.as_ptr() as usize, position: None }
+    }
+
+    /// The line and column, both counting from 1, once the error has been located.
+    pub fn position(&self) -> Option<(usize, usize)> {
+        self.position
+    }
+
+    /// Find where the error is in `input`, leaving it as it was if it's about some other text.
+    pub fn locate(mut self, input: &str) -> Self {
+        if let Some(position) = position_of(input, self.address) {
+            self.position = Some(position);
+        }
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.position {
+            Some((line, column)) => write!(f, "line {line}, column {column}: {}", self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn position_of(input: &str, address: usize) -> Option<(usize, usize)> {
+    let offset = address.checked_sub(input.as_ptr() as usize).filter(|&offset| offset <= input.len())?;
+    let before = input.get(..offset)?;
+    let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+
+    Some((before.matches('\n').count() + 1, before[line_start..].chars().count() + 1))
+}
+
+/// The line and column, both counting from 1, where `part` starts in `input`. `None` unless it's a slice of it.
+pub fn locate(input: &str, part: &str) -> Option<(usize, usize)> {
+    position_of(input, part.as_ptr() as usize)
+}
+
+/// Locate `error` in `input` if it's a [`ParseError`], otherwise hand it back untouched.
+pub fn locate_error(error: crate::Error, input: &str) -> crate::Error {
+    match error.downcast::<ParseError>() {
+        Ok(error) => error.locate(input).into(),
+        Err(error) => error,
+    }
+}
+
+/// Parse all of `text` as a `T`.
+pub fn value<T: FromStr>(text: &str) -> Result<T, ParseError>
+where
+    T::Err: Display,
+{
+    text.parse().map_err(|err| ParseError::new(text, format!("can't parse `{text}`: {err}")))
+}
+
+fn ints<T: FromStr>(text: &str, signed: bool) -> Result<Vec<T>, ParseError>
+where
+    T::Err: Display,
+{
+    let bytes = text.as_bytes();
+    let mut numbers = Vec::new();
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let start = index;
+        if signed && bytes[index] == b'-' && bytes.get(index + 1).is_some_and(u8::is_ascii_digit) {
+            index += 1;
+        }
+
+        if bytes[index].is_ascii_digit() {
+            while bytes.get(index).is_some_and(u8::is_ascii_digit) {
+                index += 1;
+            }
+            numbers.push(value(&text[start..index])?);
+        } else {
+            index += 1;
+        }
+    }
+
+    Ok(numbers)
+}
+
+/// Every run of digits in `text`, ignoring whatever is between them. A `-` right before the digits makes them
+/// negative.
+pub fn signed_ints(text: &str) -> Result<Vec<i64>, ParseError> {
+    ints(text, true)
+}
+
+/// Every run of digits in `text`, ignoring whatever is between them, `-` included.
+pub fn unsigned_ints(text: &str) -> Result<Vec<u64>, ParseError> {
+    ints(text, false)
+}
+
+/// The blocks of `input` separated by blank lines, like rules followed by updates.
+pub fn sections(input: &str) -> impl Iterator<Item = &str> {
+    input.split("\n\n").map(|section| section.trim_matches('\n')).filter(|section| !section.is_empty())
+}
+
+/// Parse each line of `input` with `parse_line`. Errors that aren't already about part of the line point at its start.
+pub fn parse_lines_with<'a, T>(
+    input: &'a str,
+    mut parse_line: impl FnMut(&'a str) -> crate::Result<T>,
+) -> crate::Result<Vec<T>> {
+    input
+        .trim()
+        .lines()
+        .map(|line| {
+            parse_line(line).map_err(|error| match error.downcast::<ParseError>() {
+                Ok(error) => error.locate(input).into(),
+                Err(error) => ParseError::new(line, error).locate(input).into(),
+            })
+        })
+        .collect()
+}
+
+/// Parse each line of `input` as a `T`, ignoring the whitespace around it.
+pub fn parse_lines<T: FromStr>(input: &str) -> crate::Result<Vec<T>>
+where
+    T::Err: Display,
+{
+    parse_lines_with(input, |line| Ok(value(line.trim())?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers() {
+        assert_eq!(unsigned_ints("Button A: X+94, Y-34").unwrap(), vec![94, 34]);
+        assert_eq!(signed_ints("p=0,4 v=3,-3 and 2-1").unwrap(), vec![0, 4, 3, -3, 2, -1]);
+        assert_eq!(signed_ints("- -x").unwrap(), Vec::<i64>::new());
+        assert_eq!(unsigned_ints("café 7").unwrap(), vec![7]);
+
+        let input = "1 2\n3 99999999999999999999\n";
+        let error = unsigned_ints(input).unwrap_err().locate(input);
+        assert_eq!(error.position(), Some((2, 3)));
+        assert!(error.to_string().starts_with("line 2, column 3: can't parse `99999999999999999999`"));
+    }
+
+    #[test]
+    fn lines_and_sections() {
+        assert_eq!(parse_lines::<u64>("1\n 2 \n3\n").unwrap(), vec![1, 2, 3]);
+
+        let error = parse_lines::<u64>("1\n2\n  x\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 3, column 3: can't parse `x`: invalid digit found in string");
+
+        let error =
+            parse_lines_with("1,2\nno comma\n", |line| line.split_once(',').ok_or_else(|| eyre::eyre!("no comma")))
+                .unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 1: no comma");
+
+        let input = "a\nb\n\nc\n\n\nd\n";
+        assert_eq!(sections(input).collect::<Vec<_>>(), vec!["a\nb", "c", "d"]);
+        assert_eq!(locate(input, sections(input).last().unwrap()), Some((7, 1)));
+        assert_eq!(locate(input, &String::from("d")), None);
+    }
+}
@@ -0,0 +1,323 @@
+use crate::{
+    answers::Answers,
+    calendar::{self, Clock, SystemClock},
+    client::Client,
+    config::Config,
+    submit::Ledger,
+    Part,
+};
+
+/// The raw `<article>` sections of a puzzle page, one per unlocked part.
+pub fn articles(html: &str) -> Vec<&str> {
+    let mut articles = Vec::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(end) = rest[start..].find("</article>") else { break };
+        articles.push(&rest[start..start + end + "</article>".len()]);
+        rest = &rest[start + end..];
+    }
+
+    articles
+}
+
+pub(crate) fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').and_then(|end| {
+            let ch = match &rest[1..end] {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                entity => entity.strip_prefix('#').and_then(|code| code.parse().ok()).and_then(char::from_u32),
+            };
+            ch.map(|ch| (ch, end))
+        });
+
+        match decoded {
+            Some((ch, end)) => {
+                out.push(ch);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// Pull the value of an attribute out of the inside of a tag, e.g. `a href="/2024"`.
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let start = tag.find(&format!("{name}=\""))? + name.len() + 2;
+    let end = tag[start..].find('"')?;
+    Some(&tag[start..start + end])
+}
+
+/// Ensure the output ends with a blank line, ready for the next block.
+fn paragraph_break(out: &mut String) {
+    while out.ends_with(' ') {
+        out.pop();
+    }
+    if !out.is_empty() {
+        while !out.ends_with("\n\n") {
+            out.push('\n');
+        }
+    }
+}
+
+/// Convert the small subset of HTML used by puzzle descriptions into Markdown.
+pub fn html_to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut rest = html;
+    let mut in_pre = false;
+    let mut in_code = false;
+    let mut links = Vec::new();
+
+    while !rest.is_empty() {
+        // text up until the next tag
+        let text_end = rest.find('<').unwrap_or(rest.len());
+        let text = decode_entities(&rest[..text_end]);
+        rest = &rest[text_end..];
+
+        if in_pre {
+            out.push_str(&text);
+        } else if !text.is_empty() {
+            let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+            if text.starts_with(char::is_whitespace) && !out.ends_with([' ', '\n']) && !out.is_empty() {
+                out.push(' ');
+            }
+            out.push_str(&collapsed);
+            if text.ends_with(char::is_whitespace) && !collapsed.is_empty() {
+                out.push(' ');
+            }
+        }
+
+        if rest.is_empty() {
+            break;
+        }
+
+        // then the tag itself
+        let Some(tag_end) = rest.find('>') else { break };
+        let tag = &rest[1..tag_end];
+        rest = &rest[tag_end + 1..];
+
+        let closing = tag.starts_with('/');
+        let name = tag.trim_start_matches('/').split([' ', '/']).next().unwrap_or_default().to_ascii_lowercase();
+
+        match (name.as_str(), closing) {
+            ("h2", false) => {
+                paragraph_break(&mut out);
+                out.push_str("## ");
+            }
+            ("h2" | "p" | "ul", true) => paragraph_break(&mut out),
+            ("p" | "ul", false) => paragraph_break(&mut out),
+            ("li", false) => out.push_str("- "),
+            ("li", true) => out.push('\n'),
+            ("br", _) => out.push('\n'),
+            ("pre", false) => {
+                paragraph_break(&mut out);
+                out.push_str("```\n");
+                in_pre = true;
+            }
+            ("pre", true) => {
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```\n\n");
+                in_pre = false;
+            }
+            ("code", _) if !in_pre => {
+                out.push('`');
+                in_code = !closing;
+            }
+            ("em", _) if !in_pre && !in_code => out.push('*'),
+            ("a", false) => {
+                links.push(attribute(tag, "href").unwrap_or_default().to_string());
+                out.push('[');
+            }
+            ("a", true) => {
+                let href = links.pop().unwrap_or_default();
+                out.push_str(&format!("]({href})"));
+            }
+            _ => {}
+        }
+    }
+
+    format!("{}\n", out.trim())
+}
+
+pub fn fetch_page(client: &Client, year: u64, day: u64) -> crate::Result<String> {
+    client.get(&format!("/{year}/day/{day}"))
+}
+
+/// Convert every unlocked part of a puzzle page into Markdown.
+pub fn to_markdown(html: &str) -> crate::Result<String> {
+    let articles = articles(html);
+    eyre::ensure!(!articles.is_empty(), "no puzzle description found on the page");
+
+    Ok(articles.into_iter().map(html_to_markdown).collect::<Vec<_>>().join("\n"))
+}
+
+/// Whether part one is known to be solved, by a submission from here or an answer from the website in `answers.toml`.
+fn part_one_solved(config: &Config, year: u64, day: u64) -> crate::Result<bool> {
+    Ok(Ledger::open(config.ledger_path())?.solved(year, day, Part::One)
+        || Answers::open(config.answers_path())?.get(year, day, Part::One).is_some())
+}
+
+/// Read the cached description of a puzzle, fetching it when it's missing or when part two has since unlocked.
+///
+/// Examples found on the page are stored along the way, see [`crate::examples`].
+pub fn get_puzzle_with(
+    config: &Config,
+    clock: &dyn Clock,
+    year: u64,
+    day: u64,
+    refresh: bool,
+) -> crate::Result<String> {
+    // validate input
+    calendar::ensure_unlocked(clock, year, day)?;
+
+    let path = config.puzzle_path(year, day);
+
+    let stale = match std::fs::read_to_string(&path) {
+        Ok(cached) => refresh || (!cached.contains("--- Part Two ---") && part_one_solved(config, year, day)?),
+        Err(_) => true,
+    };
+
+    if stale {
+        tracing::info!("fetching the y{year}d{day:0>2} puzzle description from the internet");
+        let html = fetch_page(&Client::new(config)?, year, day)?;
+
+        crate::write_atomic(&path, &to_markdown(&html)?)?;
+
+        crate::examples::save(config, year, day, &crate::examples::extract(&html))?;
+    }
+
+    Ok(std::fs::read_to_string(&path)?)
+}
+
+pub fn get_puzzle(config: &Config, year: u64, day: u64, refresh: bool) -> crate::Result<String> {
+    get_puzzle_with(config, &SystemClock, year, day, refresh)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        submit::{Attempt, Verdict},
+        testing::StandIn,
+    };
+
+    const PAGE: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Historian Hysteria ---</h2><p>The <em>Chief Historian</em> is always present for the big Christmas sleigh launch.</p>
+<p>For example:</p>
+<pre><code>3   4
+4   3
+</code></pre>
+<ul><li>The smallest number in the left list is <code>1</code>.</li></ul>
+<p>Your actual left and right lists contain many location IDs. What is the <em>total distance</em>? See <a href="/2024/about">about</a> &amp; <code>&lt;3</code>.</p>
+</article>
+<p>Your puzzle answer was <code>1234</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>The answer is <code><em>31</em></code>.</p></article>
+</main></body></html>"#;
+
+    /// The same page as it looks before part one is solved.
+    fn part_one_page() -> String {
+        let part_two = PAGE.find("<article class=\"day-desc\"><h2 id=\"part2\">").unwrap();
+        let end = PAGE[part_two..].find("</article>").unwrap() + "</article>".len();
+        format!("{}{}", &PAGE[..part_two], &PAGE[part_two + end..])
+    }
+
+    #[test]
+    fn convert_html() {
+        let markdown = articles(PAGE).into_iter().map(html_to_markdown).collect::<Vec<_>>().join("\n");
+
+        assert_eq!(
+            markdown,
+            "## --- Day 1: Historian Hysteria ---\n\
+            \n\
+            The *Chief Historian* is always present for the big Christmas sleigh launch.\n\
+            \n\
+            For example:\n\
+            \n\
+            ```\n\
+            3   4\n\
+            4   3\n\
+            ```\n\
+            \n\
+            - The smallest number in the left list is `1`.\n\
+            \n\
+            Your actual left and right lists contain many location IDs. What is the *total distance*? \
+            See [about](/2024/about) & `<3`.\n\
+            \n\
+            ## --- Part Two ---\n\
+            \n\
+            The answer is `31`.\n"
+        );
+    }
+
+    #[test]
+    fn fetch_description() {
+        let server = StandIn::serve(vec![(200, PAGE)]);
+        let client = Client::new(&server.config()).unwrap();
+
+        let markdown = to_markdown(&fetch_page(&client, 2024, 1).unwrap()).unwrap();
+        assert!(markdown.starts_with("## --- Day 1: Historian Hysteria ---"));
+        assert!(markdown.contains("--- Part Two ---"));
+        assert_eq!(server.requests()[0].path, "/2024/day/1");
+    }
+
+    #[test]
+    fn cache_until_part_two() {
+        let part_one = part_one_page();
+        let server = StandIn::serve(vec![(200, &part_one), (200, PAGE)]);
+        let config = server.config();
+
+        let markdown = get_puzzle(&config, 2024, 1, false).unwrap();
+        assert!(!markdown.contains("--- Part Two ---"));
+        assert!(config.puzzle_dir.join("2024").join("01.md").is_file());
+        assert_eq!(crate::examples::load(&config, 2024, 1).unwrap()[0].input, "3   4\n4   3\n");
+
+        // cached until part one is solved
+        get_puzzle(&config, 2024, 1, false).unwrap();
+        assert_eq!(server.requests().len(), 1);
+
+        let attempt =
+            Attempt { at: 0, year: 2024, day: 1, part: Part::One, verdict: Verdict::Correct, answer: "11".to_string() };
+        Ledger::open(config.ledger_path()).unwrap().record(attempt).unwrap();
+
+        assert!(get_puzzle(&config, 2024, 1, false).unwrap().contains("--- Part Two ---"));
+        get_puzzle(&config, 2024, 1, false).unwrap();
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[test]
+    fn refresh_for_answers_from_the_website() {
+        let part_one = part_one_page();
+        let server = StandIn::serve(vec![(200, &part_one), (200, PAGE)]);
+        let config = server.config();
+        let clock = crate::testing::FakeClock::at(1_733_029_200); // 2024-12-01T05:00:00Z
+
+        assert!(!get_puzzle_with(&config, &clock, 2024, 1, false).unwrap().contains("--- Part Two ---"));
+
+        // solved on the website, so only answers.toml knows
+        Answers::open(config.answers_path()).unwrap().record(2024, 1, Part::One, "11").unwrap();
+        assert!(get_puzzle_with(&config, &clock, 2024, 1, false).unwrap().contains("--- Part Two ---"));
+
+        assert!(get_puzzle_with(&config, &clock, 2024, 2, false).unwrap_err().to_string().contains("hasn't unlocked"));
+        assert!(get_puzzle_with(&config, &clock, 2024, 26, false).is_err());
+        assert_eq!(server.requests().len(), 2);
+    }
+}
@@ -0,0 +1,225 @@
+use std::{fmt::Write, str::FromStr, time::Duration};
+
+use serde::Serialize;
+
+use crate::{solution::label, Answer, Part};
+
+/// How the runner prints results: a table for people, or records for scripts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Table,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(Format::Table),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => eyre::bail!("format must be `table`, `json` or `csv`"),
+        }
+    }
+}
+
+/// One line of structured output, a single part of a single day.
+#[derive(Debug, Serialize)]
+pub struct Record {
+    pub year: u64,
+    pub day: u64,
+    pub part: u64,
+    pub answer: String,
+    /// Parsing is shared by both parts, so both records have the same parse time.
+    pub parse_ns: u64,
+    pub elapsed_ns: u64,
+    /// `correct` or `wrong` when there's a known answer, otherwise `unverified`.
+    pub status: &'static str,
+}
+
+/// One part of a run: what it answered, how long it took and whether that matched the known answer.
+#[derive(Debug, Clone)]
+pub struct PartRun {
+    pub part: Part,
+    pub answer: Answer,
+    pub elapsed: Duration,
+    pub expected: Option<String>,
+}
+
+impl PartRun {
+    /// `None` when there's no known answer to compare against.
+    pub fn verified(&self) -> Option<bool> {
+        self.expected.as_ref().map(|expected| self.answer == **expected)
+    }
+}
+
+/// A day's run, parsing once then solving each requested part.
+#[derive(Debug, Clone)]
+pub struct DayRun {
+    pub year: u64,
+    pub day: u64,
+    pub parse: Duration,
+    pub parts: Vec<PartRun>,
+}
+
+impl DayRun {
+    pub fn elapsed(&self) -> Duration {
+        self.parse + self.parts.iter().map(|part| part.elapsed).sum::<Duration>()
+    }
+
+    pub fn part(&self, part: Part) -> Option<&PartRun> {
+        self.parts.iter().find(|run| run.part == part)
+    }
+
+    /// Parts that took longer than `budget`, as labels.
+    pub fn over_budget(&self, budget: Duration) -> Vec<String> {
+        self.parts.iter().filter(|run| run.elapsed > budget).map(|run| label(self.year, self.day, run.part)).collect()
+    }
+}
+
+/// Every part of every run as a [`Record`].
+pub fn records(runs: &[DayRun]) -> Vec<Record> {
+    let nanos = |duration: Duration| u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+
+    runs.iter()
+        .flat_map(|run| {
+            run.parts.iter().map(|part| Record {
+                year: run.year,
+                day: run.day,
+                part: part.part.number(),
+                answer: part.answer.to_string(),
+                parse_ns: nanos(run.parse),
+                elapsed_ns: nanos(part.elapsed),
+                status: match part.verified() {
+                    Some(true) => "correct",
+                    Some(false) => "wrong",
+                    None => "unverified",
+                },
+            })
+        })
+        .collect()
+}
+
+/// Render runs in the given format.
+pub fn render(runs: &[DayRun], format: Format, budget: Option<Duration>) -> crate::Result<String> {
+    match format {
+        Format::Table => Ok(table(runs, budget)),
+        Format::Json => Ok(serde_json::to_string_pretty(&records(runs))? + "\n"),
+        Format::Csv => {
+            let mut writer = csv::Writer::from_writer(Vec::new());
+            for record in records(runs) {
+                writer.serialize(record)?;
+            }
+            Ok(String::from_utf8(writer.into_inner()?)?)
+        }
+    }
+}
+
+fn timing(elapsed: Duration) -> String {
+    format!("{elapsed:.1?}")
+}
+
+/// A table of timings for every day, with a total. Parts over `budget` are marked with `!`.
+pub fn table(runs: &[DayRun], budget: Option<Duration>) -> String {
+    let over = |elapsed: Duration| budget.is_some_and(|budget| elapsed > budget);
+
+    let cell = |run: &DayRun, part: Part| match run.part(part) {
+        Some(part) => {
+            let mark = match part.verified() {
+                Some(true) => "✓",
+                Some(false) => "✗",
+                None => " ",
+            };
+            let flag = if over(part.elapsed) { "!" } else { " " };
+            format!("{:>10}{flag}{mark}", timing(part.elapsed))
+        }
+        None => format!("{:>10}  ", "-"),
+    };
+
+    let mut out = format!("{:<9} {:>10} {:>10}   {:>10}   {:>10}\n", "day", "parse", "part one", "part two", "total");
+
+    for run in runs {
+        let _ = writeln!(
+            out,
+            "{:<9} {:>10} {} {} {:>10}",
+            format!("y{}d{:0>2}", run.year, run.day),
+            timing(run.parse),
+            cell(run, Part::One),
+            cell(run, Part::Two),
+            timing(run.elapsed()),
+        );
+    }
+
+    let total = runs.iter().map(DayRun::elapsed).sum::<Duration>();
+    let _ = writeln!(out, "{:<9} {:>10} {:>12} {:>12} {:>10}", "total", "", "", "", timing(total));
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn runs() -> Vec<DayRun> {
+        let part = |part, millis, expected: Option<&str>| PartRun {
+            part,
+            answer: Answer::from(11u64),
+            elapsed: Duration::from_millis(millis),
+            expected: expected.map(String::from),
+        };
+
+        vec![
+            DayRun {
+                year: 2024,
+                day: 1,
+                parse: Duration::from_millis(1),
+                parts: vec![part(Part::One, 2, Some("11")), part(Part::Two, 150, Some("31"))],
+            },
+            DayRun { year: 2024, day: 2, parse: Duration::from_millis(3), parts: vec![part(Part::Two, 4, None)] },
+        ]
+    }
+
+    #[test]
+    fn timing_table() {
+        let runs = runs();
+
+        assert_eq!(runs[0].over_budget(Duration::from_millis(100)), vec!["y2024d01p02"]);
+        assert_eq!(
+            table(&runs, Some(Duration::from_millis(100))),
+            [
+                "day            parse   part one     part two        total",
+                "y2024d01       1.0ms      2.0ms ✓    150.0ms!✗    153.0ms",
+                "y2024d02       3.0ms          -        4.0ms        7.0ms",
+                "total                                             160.0ms",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn structured_output() {
+        let csv = render(&runs(), "csv".parse().unwrap(), None).unwrap();
+        assert_eq!(
+            csv,
+            [
+                "year,day,part,answer,parse_ns,elapsed_ns,status",
+                "2024,1,1,11,1000000,2000000,correct",
+                "2024,1,2,11,1000000,150000000,wrong",
+                "2024,2,2,11,3000000,4000000,unverified",
+                "",
+            ]
+            .join("\n")
+        );
+
+        let json = render(&runs(), Format::Json, None).unwrap();
+        let records: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(records[1]["status"], "wrong");
+        assert_eq!(records[2]["elapsed_ns"], 4_000_000);
+
+        assert!("yaml".parse::<Format>().is_err());
+    }
+}
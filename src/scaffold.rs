@@ -0,0 +1,250 @@
+use std::path::{Path, PathBuf};
+
+use eyre::OptionExt;
+
+const DAY_TEMPLATE: &str = r#"use crate::Answer;
+
+pub fn parse(input: &str) -> crate::Result<Vec<String>> {
+    Ok(input.lines().map(String::from).collect())
+}
+
+pub fn get_input() -> crate::Result<Vec<String>> {
+    parse(&crate::get_input({{YEAR}}, {{DAY}})?)
+}
+
+pub fn part_one(_input: &[String]) -> crate::Result<Answer> {
+    Ok(0.into())
+}
+
+pub fn part_two(_input: &[String]) -> crate::Result<Answer> {
+    Ok(0.into())
+}
+
+pub struct Day;
+
+impl crate::Solution for Day {
+    const YEAR: u64 = {{YEAR}};
+    const DAY: u64 = {{DAY}};
+
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> crate::Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part_one(input: &Self::Input) -> crate::Result<Answer> {
+        part_one(input)
+    }
+
+    fn part_two(input: &Self::Input) -> crate::Result<Answer> {
+        part_two(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn y{{YEAR}}d{{DD}}p01() {
+        crate::testing::verify_answer(&Day, crate::Part::One);
+    }
+
+    #[test]
+    fn y{{YEAR}}d{{DD}}p02() {
+        crate::testing::verify_answer(&Day, crate::Part::Two);
+    }
+
+    #[test]
+    fn y{{YEAR}}d{{DD}}p01_examples() {
+        crate::testing::verify_examples(&Day, crate::Part::One);
+    }
+
+    #[test]
+    fn y{{YEAR}}d{{DD}}p02_examples() {
+        crate::testing::verify_examples(&Day, crate::Part::Two);
+    }
+}
+"#;
+
+const BENCH_TEMPLATE: &str = r#"use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+
+fn y{{YEAR}}d{{DD}}p01(c: &mut Criterion) {
+    let input = aoc::y{{YEAR}}::d{{DD}}::get_input().unwrap();
+    c.bench_function("y{{YEAR}}d{{DD}}p01", |b| b.iter(|| aoc::y{{YEAR}}::d{{DD}}::part_one(black_box(&input))));
+}
+
+fn y{{YEAR}}d{{DD}}p02(c: &mut Criterion) {
+    let input = aoc::y{{YEAR}}::d{{DD}}::get_input().unwrap();
+    c.bench_function("y{{YEAR}}d{{DD}}p02", |b| b.iter(|| aoc::y{{YEAR}}::d{{DD}}::part_two(black_box(&input))));
+}
+
+criterion_group!(benches, y{{YEAR}}d{{DD}}p01, y{{YEAR}}d{{DD}}p02);
+criterion_main!(benches);
+"#;
+
+fn render(template: &str, year: u64, day: u64) -> String {
+    template
+        .replace("{{YEAR}}", &year.to_string())
+        .replace("{{DAY}}", &day.to_string())
+        .replace("{{DD}}", &format!("{day:0>2}"))
+}
+
+/// Add `pub mod {name};` next to the other `pub mod {prefix}..` lines, keeping them sorted.
+fn add_module(source: &str, prefix: &str, name: &str) -> String {
+    let line = format!("pub mod {name};");
+    let mut lines = source.lines().map(String::from).collect::<Vec<_>>();
+
+    let siblings = (0..lines.len()).filter(|&i| lines[i].starts_with(&format!("pub mod {prefix}"))).collect::<Vec<_>>();
+    let index = siblings.iter().find(|&&i| lines[i] > line).copied().or(siblings.last().map(|i| i + 1)).unwrap_or(0);
+
+    lines.insert(index, line);
+    lines.join("\n") + "\n"
+}
+
+/// Add `entry` to the `&[...]` list that follows `marker`, keeping it sorted.
+fn add_to_list(source: &str, marker: &str, entry: &str) -> crate::Result<String> {
+    let start = source.find(marker).ok_or_eyre(format!("unable to find `{marker}`"))?;
+    let equals = start + source[start..].find('=').ok_or_eyre(format!("`{marker}` isn't assigned"))?;
+    let open = equals + source[equals..].find("&[").ok_or_eyre("unable to find the start of the list")? + 2;
+    let close = open + source[open..].find("];").ok_or_eyre("unable to find the end of the list")?;
+
+    let mut entries = source[open..close].split(',').map(str::trim).filter(|e| !e.is_empty()).collect::<Vec<_>>();
+    entries.push(entry);
+    entries.sort_unstable();
+    entries.dedup();
+
+    Ok(format!("{}{}{}", &source[..open], entries.join(", "), &source[close..]))
+}
+
+fn ensure_missing(path: &Path) -> crate::Result<()> {
+    eyre::ensure!(!path.exists(), "{} already exists, not overwriting it", path.display());
+    Ok(())
+}
+
+/// Generate a new day in the repository at `root`: its module, its registration and a bench.
+///
+/// Refuses to touch anything if the day, or its bench, already exists. Returns every file written.
+pub fn new_day(root: &Path, year: u64, day: u64) -> crate::Result<Vec<PathBuf>> {
+    let bench_name = format!("y{year}d{day:0>2}");
+    let year_dir = root.join("src").join(format!("y{year}"));
+    let module_path = year_dir.join(format!("d{day:0>2}.rs"));
+    let mod_path = year_dir.join("mod.rs");
+    let bench_path = root.join("benches").join(format!("{bench_name}.rs"));
+    let cargo_path = root.join("Cargo.toml");
+
+    ensure_missing(&module_path)?;
+    ensure_missing(&bench_path)?;
+
+    let cargo = std::fs::read_to_string(&cargo_path)?;
+    eyre::ensure!(!cargo.contains(&format!("\"{bench_name}\"")), "Cargo.toml already has a {bench_name} bench");
+
+    let mut written = Vec::new();
+
+    // register the day, creating the year if needed
+    let day_entry = format!("&d{day:0>2}::Day");
+    if mod_path.is_file() {
+        let source = add_module(&std::fs::read_to_string(&mod_path)?, "d", &format!("d{day:0>2}"));
+        std::fs::write(&mod_path, add_to_list(&source, "pub static SOLUTIONS", &day_entry)?)?;
+    } else {
+        std::fs::create_dir_all(&year_dir)?;
+        std::fs::write(
+            &mod_path,
+            format!("pub mod d{day:0>2};\n\npub static SOLUTIONS: &[&dyn crate::Solver] = &[{day_entry}];\n"),
+        )?;
+
+        let lib_path = root.join("src").join("lib.rs");
+        std::fs::write(&lib_path, add_module(&std::fs::read_to_string(&lib_path)?, "y", &format!("y{year}")))?;
+
+        let registry_path = root.join("src").join("solution.rs");
+        let registry = std::fs::read_to_string(&registry_path)?;
+        std::fs::write(&registry_path, add_to_list(&registry, "static YEARS", &format!("crate::y{year}::SOLUTIONS"))?)?;
+
+        written.extend([lib_path, registry_path]);
+    }
+    written.push(mod_path);
+
+    std::fs::write(&module_path, render(DAY_TEMPLATE, year, day))?;
+    written.push(module_path);
+
+    std::fs::create_dir_all(bench_path.parent().ok_or_eyre("unable to get parent dir of target path")?)?;
+    std::fs::write(&bench_path, render(BENCH_TEMPLATE, year, day))?;
+    written.push(bench_path);
+
+    let bench_entry = format!("\n[[bench]]\nharness = false\nname    = \"{bench_name}\"\n");
+    std::fs::write(&cargo_path, format!("{}\n{bench_entry}", cargo.trim_end()))?;
+    written.push(cargo_path);
+
+    Ok(written)
+}
+
+/// Run rustfmt over the Rust files in `paths`, the lists edited in place are written on a single line.
+pub fn format(paths: &[PathBuf]) -> crate::Result<()> {
+    let sources = paths.iter().filter(|path| path.extension().is_some_and(|ext| ext == "rs")).collect::<Vec<_>>();
+    let status = std::process::Command::new("rustfmt").args(["--edition", "2021"]).args(&sources).status()?;
+    eyre::ensure!(status.success(), "rustfmt failed, the generated code may need formatting");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::temp_dir;
+
+    fn fake_repo() -> PathBuf {
+        let root = temp_dir("scaffold");
+        std::fs::create_dir_all(root.join("src/y2024")).unwrap();
+        std::fs::write(
+            root.join("Cargo.toml"),
+            "[package]\nname = \"aoc\"\n\n[[bench]]\nharness = false\nname    = \"y2024d01\"\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("src/lib.rs"), "pub mod util;\npub mod y2024;\n\npub type Error = eyre::ErrReport;\n")
+            .unwrap();
+        std::fs::write(root.join("src/solution.rs"), "static YEARS: &[&[&dyn Solver]] = &[crate::y2024::SOLUTIONS];\n")
+            .unwrap();
+        std::fs::write(
+            root.join("src/y2024/mod.rs"),
+            "pub mod d01;\npub mod d10;\n\npub static SOLUTIONS: &[&dyn crate::Solver] =\n    &[&d01::Day, &d10::Day];\n",
+        )
+        .unwrap();
+        root
+    }
+
+    fn read(root: &Path, path: &str) -> String {
+        std::fs::read_to_string(root.join(path)).unwrap()
+    }
+
+    #[test]
+    fn new_day_in_existing_year() {
+        let root = fake_repo();
+        new_day(&root, 2024, 9).unwrap();
+
+        let module = read(&root, "src/y2024/mod.rs");
+        assert!(module.contains("pub mod d01;\npub mod d09;\npub mod d10;\n"));
+        assert!(module.contains("&d01::Day, &d09::Day, &d10::Day"));
+        assert!(read(&root, "src/y2024/d09.rs").contains("parse(&crate::get_input(2024, 9)?)"));
+        // no examples yet, the generated tests have to pass anyway
+        assert!(read(&root, "src/y2024/d09.rs").contains("crate::testing::verify_examples(&Day, crate::Part::Two);"));
+        assert!(read(&root, "benches/y2024d09.rs").contains("aoc::y2024::d09::part_two"));
+        assert!(read(&root, "Cargo.toml").ends_with("[[bench]]\nharness = false\nname    = \"y2024d09\"\n"));
+
+        // never overwrite existing work
+        assert!(new_day(&root, 2024, 9).is_err());
+        std::fs::remove_file(root.join("src/y2024/d09.rs")).unwrap();
+        assert!(new_day(&root, 2024, 9).is_err());
+    }
+
+    #[test]
+    fn new_day_in_new_year() {
+        let root = fake_repo();
+        new_day(&root, 2025, 1).unwrap();
+
+        assert!(read(&root, "src/lib.rs").contains("pub mod y2024;\npub mod y2025;\n"));
+        assert!(read(&root, "src/solution.rs").contains("crate::y2024::SOLUTIONS, crate::y2025::SOLUTIONS"));
+        assert!(read(&root, "src/y2025/mod.rs").contains("&[&d01::Day]"));
+        assert!(read(&root, "src/y2025/d01.rs").contains("const YEAR: u64 = 2025;"));
+    }
+}
@@ -0,0 +1,303 @@
+//! Searches over anything with neighbours, grid positions or whole puzzle states alike.
+//!
+//! Each search takes a start state and a closure listing the states reachable from a given one, with a cost per step
+//! for the weighted searches.
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+/// Everything a search reached: the cost to get to each state, and the step it came from.
+#[derive(Debug, Clone)]
+pub struct Search<S> {
+    start: S,
+    costs: HashMap<S, u64>,
+    parents: HashMap<S, S>,
+}
+
+impl<S: Clone + Eq + Hash> Search<S> {
+    fn new(start: S) -> Self {
+        Self { costs: HashMap::from([(start.clone(), 0)]), parents: HashMap::new(), start }
+    }
+
+    /// The cost of the cheapest way found to `state`, `None` if it wasn't reached.
+    pub fn cost(&self, state: &S) -> Option<u64> {
+        self.costs.get(state).copied()
+    }
+
+    /// The states from the start to `state` inclusive, `None` if it wasn't reached.
+    pub fn path(&self, state: &S) -> Option<Vec<S>> {
+        if !self.costs.contains_key(state) {
+            return None;
+        }
+
+        let mut path = vec![state.clone()];
+        while let Some(parent) = self.parents.get(&path[path.len() - 1]) {
+            path.push(parent.clone());
+        }
+
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every state reached, the start included.
+    pub fn reached(&self) -> impl Iterator<Item = &S> + '_ {
+        self.costs.keys()
+    }
+
+    pub fn start(&self) -> &S {
+        &self.start
+    }
+}
+
+/// Breadth first from `start`, every step costing one, until `goal` is reached or there's nowhere left to go.
+///
+/// Returns the search and the goal reached, if any. Pass `|_| false` to explore everything reachable.
+pub fn bfs_until<S, I>(
+    start: S,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut goal: impl FnMut(&S) -> bool,
+) -> (Search<S>, Option<S>)
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut search = Search::new(start.clone());
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(state) = queue.pop_front() {
+        if goal(&state) {
+            return (search, Some(state));
+        }
+
+        let cost = search.costs[&state] + 1;
+        for next in neighbours(&state) {
+            if !search.costs.contains_key(&next) {
+                search.costs.insert(next.clone(), cost);
+                search.parents.insert(next.clone(), state.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+
+    (search, None)
+}
+
+/// Breadth first from `start` to everything reachable, giving the fewest steps to each state.
+pub fn bfs<S, I>(start: S, neighbours: impl FnMut(&S) -> I) -> Search<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    bfs_until(start, neighbours, |_| false).0
+}
+
+/// The fewest steps from `start` to a state matching `goal`, and the path there.
+pub fn bfs_to<S, I>(start: S, neighbours: impl FnMut(&S) -> I, goal: impl FnMut(&S) -> bool) -> Option<(u64, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let (search, reached) = bfs_until(start, neighbours, goal);
+    let reached = reached?;
+    Some((search.cost(&reached)?, search.path(&reached)?))
+}
+
+/// Depth first from `start`, returning states in the order they were first visited.
+pub fn dfs<S, I>(start: S, mut neighbours: impl FnMut(&S) -> I) -> Vec<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut seen = HashSet::new();
+    let mut order = Vec::new();
+    let mut stack = vec![start];
+
+    while let Some(state) = stack.pop() {
+        if !seen.insert(state.clone()) {
+            continue;
+        }
+
+        // push in reverse so the first neighbour is visited first
+        let next = neighbours(&state).into_iter().filter(|next| !seen.contains(next)).collect::<Vec<_>>();
+        stack.extend(next.into_iter().rev());
+        order.push(state);
+    }
+
+    order
+}
+
+/// Every state reachable from `start`, e.g. a flood filled region.
+pub fn reachable<S, I>(start: S, neighbours: impl FnMut(&S) -> I) -> HashSet<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    bfs(start, neighbours).costs.into_keys().collect()
+}
+
+/// Cheapest first from `start`, guided by `heuristic`, until `goal` is reached or there's nowhere left to go.
+///
+/// With a heuristic of zero this is Dijkstra. Otherwise it's A*, and the heuristic mustn't overestimate the cost left
+/// or a worse path may be found.
+pub fn best_first<S, I>(
+    start: S,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> u64,
+    mut goal: impl FnMut(&S) -> bool,
+) -> (Search<S>, Option<S>)
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    let mut search = Search::new(start.clone());
+    let mut done = HashSet::new();
+
+    // the heap only holds indices into `pending`, so states needn't be `Ord`
+    let mut pending = vec![start.clone()];
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), 0))]);
+
+    while let Some(Reverse((_, index))) = heap.pop() {
+        let state = pending[index].clone();
+        if !done.insert(state.clone()) {
+            continue;
+        }
+
+        if goal(&state) {
+            return (search, Some(state));
+        }
+
+        let cost = search.costs[&state];
+        for (next, step) in neighbours(&state) {
+            let next_cost = cost + step;
+            if search.costs.get(&next).is_some_and(|&known| known <= next_cost) {
+                continue;
+            }
+
+            search.costs.insert(next.clone(), next_cost);
+            search.parents.insert(next.clone(), state.clone());
+            heap.push(Reverse((next_cost + heuristic(&next), pending.len())));
+            pending.push(next);
+        }
+    }
+
+    (search, None)
+}
+
+/// The cheapest cost from `start` to everything reachable.
+pub fn dijkstra<S, I>(start: S, neighbours: impl FnMut(&S) -> I) -> Search<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    best_first(start, neighbours, |_| 0, |_| false).0
+}
+
+/// The cheapest cost from `start` to a state matching `goal`, and the path there.
+pub fn dijkstra_to<S, I>(
+    start: S,
+    neighbours: impl FnMut(&S) -> I,
+    goal: impl FnMut(&S) -> bool,
+) -> Option<(u64, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    astar(start, neighbours, |_| 0, goal)
+}
+
+/// Like [`dijkstra_to`], exploring states that `heuristic` thinks are closer to the goal first.
+pub fn astar<S, I>(
+    start: S,
+    neighbours: impl FnMut(&S) -> I,
+    heuristic: impl FnMut(&S) -> u64,
+    goal: impl FnMut(&S) -> bool,
+) -> Option<(u64, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    let (search, reached) = best_first(start, neighbours, heuristic, goal);
+    let reached = reached?;
+    Some((search.cost(&reached)?, search.path(&reached)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        grid::Grid,
+        point::{Direction, Point},
+    };
+
+    const MAZE: &str = "\
+S...#
+.##.#
+.#..E
+.#.##
+...#.
+";
+
+    fn maze() -> (Grid<char>, Point, Point) {
+        let grid = Grid::parse(MAZE, |ch| ch).unwrap();
+        let (start, end) = (grid.find(&'S').unwrap(), grid.find(&'E').unwrap());
+        (grid, start, end)
+    }
+
+    fn open<'a>(grid: &'a Grid<char>) -> impl FnMut(&Point) -> Vec<Point> + 'a {
+        |&point| grid.neighbours_4(point).filter(|&next| grid.get(next) != Some(&'#')).collect()
+    }
+
+    #[test]
+    fn unweighted() {
+        let (grid, start, end) = maze();
+
+        let (steps, path) = bfs_to(start, open(&grid), |&point| point == end).unwrap();
+        assert_eq!(steps, 6);
+        assert_eq!(path.len(), 7);
+        assert_eq!((path[0], path[6]), (start, end));
+        assert!(path.windows(2).all(|step| step[0].manhattan(step[1]) == 1));
+
+        let search = bfs(start, open(&grid));
+        assert_eq!(search.cost(&Point::new(2, 4)), Some(6));
+        assert_eq!(search.cost(&Point::new(4, 4)), None);
+
+        // the bottom right corner is walled off
+        assert_eq!(reachable(start, open(&grid)).len(), 15);
+        assert_eq!(reachable(Point::new(4, 4), open(&grid)).len(), 1);
+
+        let order = dfs(start, open(&grid));
+        assert_eq!(order.len(), 15);
+        assert_eq!(order[..3], [start, Point::new(1, 0), Point::new(2, 0)]);
+    }
+
+    #[test]
+    fn weighted() {
+        let (grid, start, end) = maze();
+
+        // states are a position and a heading, turning costs 1000 like a reindeer maze
+        let moves = |&(point, direction): &(Point, Direction)| {
+            let mut moves = vec![((point, direction.turn_left()), 1000), ((point, direction.turn_right()), 1000)];
+            if grid.get(point.step(direction)).is_some_and(|&ch| ch != '#') {
+                moves.push(((point.step(direction), direction), 1));
+            }
+            moves
+        };
+
+        let (cost, path) = dijkstra_to((start, Direction::East), moves, |&(point, _)| point == end).unwrap();
+        assert_eq!(cost, 2006);
+        assert_eq!(path.last().unwrap().0, end);
+
+        let (cost, _) =
+            astar((start, Direction::East), moves, |&(point, _)| point.manhattan(end), |&(point, _)| point == end)
+                .unwrap();
+        assert_eq!(cost, 2006);
+
+        let search = dijkstra((start, Direction::East), moves);
+        assert_eq!(search.cost(&(start, Direction::West)), Some(2000));
+        assert_eq!(search.path(&(start, Direction::South)).unwrap().len(), 2);
+        assert!(dijkstra_to((start, Direction::East), moves, |&(point, _)| point == Point::new(4, 4)).is_none());
+    }
+}
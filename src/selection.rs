@@ -0,0 +1,126 @@
+use std::{collections::BTreeSet, str::FromStr};
+
+use itertools::Itertools;
+
+/// A set of years or days picked on the command line, e.g. `6`, `1..=5`, `1-5`, `1,3,5` or `all`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Selection {
+    All,
+    Only(BTreeSet<u64>),
+}
+
+impl Selection {
+    /// Narrow `available` down to the values picked by this selection, in ascending order.
+    pub fn resolve(&self, available: &[u64]) -> Vec<u64> {
+        match self {
+            Selection::All => available.iter().copied().sorted().dedup().collect(),
+            Selection::Only(values) => values.iter().copied().filter(|v| available.contains(v)).collect(),
+        }
+    }
+
+    /// Values that were asked for explicitly but aren't in `available`.
+    pub fn missing(&self, available: &[u64]) -> Vec<u64> {
+        match self {
+            Selection::All => Vec::new(),
+            Selection::Only(values) => values.iter().copied().filter(|v| !available.contains(v)).collect(),
+        }
+    }
+
+    /// Ensure every explicitly picked value falls within `bounds`.
+    pub fn ensure_within(&self, bounds: std::ops::RangeInclusive<u64>, what: &str) -> crate::Result<()> {
+        if let Selection::Only(values) = self {
+            if let Some(value) = values.iter().find(|v| !bounds.contains(v)) {
+                eyre::bail!(
+                    "{what} {value} is out of range, {what}s must be between {} and {}",
+                    bounds.start(),
+                    bounds.end()
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The most values a single range can cover, far more than there are days or years, so typos fail fast instead of
+/// filling memory.
+const MAX_RANGE: u64 = 100;
+
+fn parse_value(value: &str) -> crate::Result<u64> {
+    value.trim().parse().map_err(|_| eyre::eyre!("`{value}` isn't a number"))
+}
+
+impl FromStr for Selection {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().eq_ignore_ascii_case("all") {
+            return Ok(Selection::All);
+        }
+
+        let mut values = BTreeSet::new();
+
+        for item in s.split(',') {
+            // ranges are inclusive, either `1..=5` or `1-5`
+            let range = item.split_once("..=").or_else(|| item.split_once('-'));
+
+            match range {
+                Some((start, end)) => {
+                    let (start, end) = (parse_value(start)?, parse_value(end)?);
+                    eyre::ensure!(start <= end, "range `{item}` is backwards");
+                    eyre::ensure!(
+                        end - start < MAX_RANGE,
+                        "range `{item}` is too long, it can cover {MAX_RANGE} at most"
+                    );
+                    values.extend(start..=end);
+                }
+                None => {
+                    values.insert(parse_value(item)?);
+                }
+            }
+        }
+
+        Ok(Selection::Only(values))
+    }
+}
+
+impl FromStr for crate::Part {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "1" => Ok(crate::Part::One),
+            "2" => Ok(crate::Part::Two),
+            other => Err(eyre::eyre!("part must be 1 or 2, not `{other}`")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_selection() {
+        assert_eq!("all".parse::<Selection>().unwrap(), Selection::All);
+        assert_eq!("6".parse::<Selection>().unwrap(), Selection::Only([6].into()));
+        assert_eq!("1..=3".parse::<Selection>().unwrap(), Selection::Only([1, 2, 3].into()));
+        assert_eq!("1-3,7".parse::<Selection>().unwrap(), Selection::Only([1, 2, 3, 7].into()));
+        assert!("3-1".parse::<Selection>().is_err());
+        assert!("six".parse::<Selection>().is_err());
+        assert!("1-99999999999".parse::<Selection>().unwrap_err().to_string().contains("too long"));
+        assert!("0-18446744073709551615".parse::<Selection>().is_err());
+    }
+
+    #[test]
+    fn resolve_selection() {
+        let available = [1, 2, 3, 4];
+        let selection = "3-6".parse::<Selection>().unwrap();
+
+        assert_eq!(selection.resolve(&available), vec![3, 4]);
+        assert_eq!(selection.missing(&available), vec![5, 6]);
+        assert_eq!(Selection::All.resolve(&available), vec![1, 2, 3, 4]);
+        assert!(selection.ensure_within(1..=25, "day").is_ok());
+        assert!("26".parse::<Selection>().unwrap().ensure_within(1..=25, "day").is_err());
+    }
+}
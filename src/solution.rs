@@ -0,0 +1,142 @@
+use std::{any::Any, collections::BTreeMap, time::Instant};
+
+use eyre::OptionExt;
+use itertools::Itertools;
+
+use crate::{
+    report::{DayRun, PartRun},
+    Answer,
+};
+
+/// One half of a day's puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u64 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+/// The name used for a part in logs and tests, e.g. `y2024d06p02`.
+pub fn label(year: u64, day: u64, part: Part) -> String {
+    format!("y{year}d{day:0>2}p{:0>2}", part.number())
+}
+
+/// A day's puzzle: parse the input once, then solve both parts against it.
+pub trait Solution {
+    const YEAR: u64;
+    const DAY: u64;
+
+    type Input: Send + Sync + 'static;
+
+    fn parse(input: &str) -> crate::Result<Self::Input>;
+    fn part_one(input: &Self::Input) -> crate::Result<Answer>;
+    fn part_two(input: &Self::Input) -> crate::Result<Answer>;
+}
+
+/// Object safe view of a [`Solution`], so every day can live in the [`registry`].
+pub trait Solver: Sync {
+    fn year(&self) -> u64;
+    fn day(&self) -> u64;
+    fn parse(&self, input: &str) -> crate::Result<Box<dyn Any + Send + Sync>>;
+    fn solve(&self, input: &dyn Any, part: Part) -> crate::Result<Answer>;
+
+    /// Parse `input`, then solve and log the requested parts, marking them ✓ or ✗ when `answers` has them.
+    fn run(&self, input: &str, parts: &[Part], answers: Option<&crate::answers::Answers>) -> crate::Result<DayRun> {
+        let started = Instant::now();
+        let input = self.parse(input)?;
+        let mut run = DayRun { year: self.year(), day: self.day(), parse: started.elapsed(), parts: Vec::new() };
+
+        for &part in parts {
+            let label = label(self.year(), self.day(), part);
+            let started = Instant::now();
+            let answer = self.solve(input.as_ref(), part)?;
+            let elapsed = started.elapsed();
+            let expected = answers.and_then(|answers| answers.get(self.year(), self.day(), part)).map(String::from);
+
+            match &expected {
+                Some(expected) if answer == **expected => tracing::info!("{label}: {answer} ✓ ({elapsed:.1?})"),
+                Some(expected) => tracing::warn!("{label}: {answer} ✗ expected {expected} ({elapsed:.1?})"),
+                None => tracing::info!("{label}: {answer} ({elapsed:.1?})"),
+            }
+
+            run.parts.push(PartRun { part, answer, elapsed, expected });
+        }
+
+        Ok(run)
+    }
+}
+
+impl<S: Solution + Sync> Solver for S {
+    fn year(&self) -> u64 {
+        S::YEAR
+    }
+
+    fn day(&self) -> u64 {
+        S::DAY
+    }
+
+    fn parse(&self, input: &str) -> crate::Result<Box<dyn Any + Send + Sync>> {
+        // point parse errors at the line and column of the whole input they came from
+        Ok(Box::new(S::parse(input).map_err(|error| crate::parsing::locate_error(error, input))?))
+    }
+
+    fn solve(&self, input: &dyn Any, part: Part) -> crate::Result<Answer> {
+        let input = input.downcast_ref::<S::Input>().ok_or_eyre("input was parsed by a different solution")?;
+
+        match part {
+            Part::One => S::part_one(input),
+            Part::Two => S::part_two(input),
+        }
+    }
+}
+
+/// Each year's solutions, see `y2024::SOLUTIONS`.
+static YEARS: &[&[&dyn Solver]] = &[crate::y2024::SOLUTIONS];
+
+/// Every solution, keyed by `(year, day)`.
+pub fn registry() -> BTreeMap<(u64, u64), &'static dyn Solver> {
+    YEARS.iter().flat_map(|year| year.iter()).map(|&solver| ((solver.year(), solver.day()), solver)).collect()
+}
+
+/// Look up the solution for a single day.
+pub fn find(year: u64, day: u64) -> Option<&'static dyn Solver> {
+    registry().get(&(year, day)).copied()
+}
+
+/// Years with at least one solution.
+pub fn years() -> Vec<u64> {
+    registry().keys().map(|(year, _)| *year).dedup().collect()
+}
+
+/// Days with a solution for the given year.
+pub fn days(year: u64) -> Vec<u64> {
+    registry().keys().filter(|(y, _)| *y == year).map(|(_, day)| *day).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_has_no_duplicates() {
+        let count = YEARS.iter().map(|year| year.len()).sum::<usize>();
+        assert_eq!(registry().len(), count, "two solutions claim the same (year, day)");
+    }
+
+    #[test]
+    fn labels() {
+        assert_eq!(label(2024, 4, Part::Two), "y2024d04p02");
+        assert_eq!(find(2024, 6).map(|solver| solver.day()), Some(6));
+        assert!(find(2024, 26).is_none());
+    }
+}
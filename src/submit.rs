@@ -0,0 +1,261 @@
+use std::{
+    fmt::Display,
+    io::Write,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use eyre::OptionExt;
+
+use crate::{client::Client, Part};
+
+/// What adventofcode.com made of a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    RateLimited(Duration),
+    AlreadySolved,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::TooHigh => write!(f, "too-high"),
+            Verdict::TooLow => write!(f, "too-low"),
+            Verdict::RateLimited(wait) => write!(f, "rate-limited:{}", wait.as_secs()),
+            Verdict::AlreadySolved => write!(f, "already-solved"),
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "wrong" => Ok(Verdict::Wrong),
+            "too-high" => Ok(Verdict::TooHigh),
+            "too-low" => Ok(Verdict::TooLow),
+            "already-solved" => Ok(Verdict::AlreadySolved),
+            other => match other.strip_prefix("rate-limited:") {
+                Some(wait) => Ok(Verdict::RateLimited(Duration::from_secs(wait.parse()?))),
+                None => Err(eyre::eyre!("unknown verdict: `{other}`")),
+            },
+        }
+    }
+}
+
+/// Read the verdict out of the page returned by `/{year}/day/{day}/answer`.
+pub fn parse_reply(html: &str) -> crate::Result<Verdict> {
+    if html.contains("That's the right answer") {
+        return Ok(Verdict::Correct);
+    }
+
+    if html.contains("That's not the right answer") {
+        if html.contains("your answer is too high") {
+            return Ok(Verdict::TooHigh);
+        }
+        if html.contains("your answer is too low") {
+            return Ok(Verdict::TooLow);
+        }
+        return Ok(Verdict::Wrong);
+    }
+
+    if html.contains("You gave an answer too recently") {
+        // e.g. "You have 1m 23s left to wait."
+        let re = regex::Regex::new(r"You have (?:(\d+)m)?\s*(?:(\d+)s)? left to wait")?;
+        let captures = re.captures(html).ok_or_eyre("unable to find how long to wait")?;
+        let minutes = captures.get(1).map(|m| m.as_str().parse()).transpose()?.unwrap_or(0u64);
+        let seconds = captures.get(2).map(|m| m.as_str().parse()).transpose()?.unwrap_or(0u64);
+
+        return Ok(Verdict::RateLimited(Duration::from_secs(minutes * 60 + seconds)));
+    }
+
+    if html.contains("You don't seem to be solving the right level") {
+        return Ok(Verdict::AlreadySolved);
+    }
+
+    Err(eyre::eyre!("unrecognised reply from adventofcode.com"))
+}
+
+/// A single submission, as stored in the [`Ledger`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    pub at: u64,
+    pub year: u64,
+    pub day: u64,
+    pub part: Part,
+    pub verdict: Verdict,
+    pub answer: String,
+}
+
+/// Every answer submitted so far, one tab separated line per attempt.
+pub struct Ledger {
+    path: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+impl Ledger {
+    pub fn open(path: impl AsRef<Path>) -> crate::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut attempts = Vec::new();
+
+        if path.is_file() {
+            for line in std::fs::read_to_string(&path)?.lines().filter(|line| !line.is_empty()) {
+                let fields = line.splitn(6, '\t').collect::<Vec<_>>();
+                let [at, year, day, part, verdict, answer] = fields[..] else {
+                    eyre::bail!("malformed ledger line: `{line}`");
+                };
+
+                attempts.push(Attempt {
+                    at: at.parse()?,
+                    year: year.parse()?,
+                    day: day.parse()?,
+                    part: part.parse()?,
+                    verdict: verdict.parse()?,
+                    answer: answer.to_string(),
+                });
+            }
+        }
+
+        Ok(Self { path, attempts })
+    }
+
+    /// Attempts made for a single part, oldest first.
+    pub fn attempts(&self, year: u64, day: u64, part: Part) -> impl Iterator<Item = &Attempt> {
+        self.attempts.iter().filter(move |a| a.year == year && a.day == day && a.part == part)
+    }
+
+    /// Whether a part has been accepted.
+    pub fn solved(&self, year: u64, day: u64, part: Part) -> bool {
+        self.attempts(year, day, part).any(|attempt| attempt.verdict == Verdict::Correct)
+    }
+
+    /// Refuse answers the ledger already knows can't be right, or submissions that would be rate limited.
+    pub fn check(&self, year: u64, day: u64, part: Part, answer: &str) -> crate::Result<()> {
+        let value = answer.parse::<i128>().ok();
+        let now = now();
+
+        for attempt in self.attempts(year, day, part) {
+            match &attempt.verdict {
+                Verdict::Correct if attempt.answer == answer => eyre::bail!("`{answer}` was already accepted"),
+                Verdict::Correct => eyre::bail!("already solved with `{}`", attempt.answer),
+                Verdict::RateLimited(wait) => {
+                    let until = attempt.at + wait.as_secs();
+                    eyre::ensure!(now >= until, "rate limited, wait another {}s", until.saturating_sub(now));
+                }
+                Verdict::AlreadySolved => {}
+                _ if attempt.answer == answer => {
+                    eyre::bail!("`{answer}` was already rejected as {}", attempt.verdict)
+                }
+                Verdict::TooHigh => {
+                    if let (Some(value), Ok(bound)) = (value, attempt.answer.parse::<i128>()) {
+                        eyre::ensure!(value < bound, "`{answer}` is too high, `{bound}` already was");
+                    }
+                }
+                Verdict::TooLow => {
+                    if let (Some(value), Ok(bound)) = (value, attempt.answer.parse::<i128>()) {
+                        eyre::ensure!(value > bound, "`{answer}` is too low, `{bound}` already was");
+                    }
+                }
+                Verdict::Wrong => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn record(&mut self, attempt: Attempt) -> crate::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let Attempt { at, year, day, part, verdict, answer } = &attempt;
+        let mut file = std::fs::OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{at}\t{year}\t{day}\t{}\t{verdict}\t{answer}", part.number())?;
+
+        self.attempts.push(attempt);
+        Ok(())
+    }
+}
+
+/// Check the answer against the ledger, post it and record whatever comes back.
+pub fn submit(
+    client: &Client,
+    ledger: &mut Ledger,
+    year: u64,
+    day: u64,
+    part: Part,
+    answer: &str,
+) -> crate::Result<Verdict> {
+    ledger.check(year, day, part, answer)?;
+
+    let level = part.number().to_string();
+    let reply = client.post_form(&format!("/{year}/day/{day}/answer"), &[("level", &level), ("answer", answer)])?;
+    let verdict = parse_reply(&reply)?;
+
+    ledger.record(Attempt { at: now(), year, day, part, verdict: verdict.clone(), answer: answer.to_string() })?;
+    Ok(verdict)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{temp_dir, StandIn};
+
+    const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high.  If you're stuck, \
+        make sure you're using the full input data. Please wait one minute before trying again.</p></article>";
+    const RATE_LIMITED: &str = "<article><p>You gave an answer too recently; you have to wait after submitting an \
+        answer before trying again.  You have 1m 23s left to wait.</p></article>";
+    const CORRECT: &str = "<article><p>That's the right answer!  You are <span class=\"day-success\">one gold star\
+        </span> closer to finding the Chief Historian.</p></article>";
+
+    #[test]
+    fn parse_replies() {
+        assert_eq!(parse_reply(CORRECT).unwrap(), Verdict::Correct);
+        assert_eq!(parse_reply(TOO_HIGH).unwrap(), Verdict::TooHigh);
+        assert_eq!(parse_reply(RATE_LIMITED).unwrap(), Verdict::RateLimited(Duration::from_secs(83)));
+        assert!(parse_reply("<html>something else</html>").is_err());
+    }
+
+    #[test]
+    fn submit_and_record() {
+        let server = StandIn::serve(vec![(200, TOO_HIGH), (200, CORRECT)]);
+        let client = Client::new(&server.config()).unwrap();
+        let path = temp_dir("ledger").join("submissions.tsv");
+        let mut ledger = Ledger::open(&path).unwrap();
+
+        assert_eq!(submit(&client, &mut ledger, 2024, 7, Part::Two, "500").unwrap(), Verdict::TooHigh);
+
+        // the ledger knows better than to try these
+        assert!(submit(&client, &mut ledger, 2024, 7, Part::Two, "500").is_err());
+        assert!(submit(&client, &mut ledger, 2024, 7, Part::Two, "501").is_err());
+
+        assert_eq!(submit(&client, &mut ledger, 2024, 7, Part::Two, "499").unwrap(), Verdict::Correct);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/2024/day/7/answer");
+        assert_eq!(requests[0].body, "level=2&answer=500");
+        assert_eq!(requests[0].header("cookie"), Some("session=token"));
+
+        // attempts survive a reload
+        let ledger = Ledger::open(&path).unwrap();
+        let verdicts = ledger.attempts(2024, 7, Part::Two).map(|a| a.verdict.clone()).collect::<Vec<_>>();
+        assert_eq!(verdicts, vec![Verdict::TooHigh, Verdict::Correct]);
+        assert!(ledger.check(2024, 7, Part::Two, "498").is_err());
+        assert!(ledger.check(2024, 7, Part::One, "498").is_ok());
+    }
+}
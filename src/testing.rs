@@ -0,0 +1,187 @@
+//! Helpers shared by tests: answer checks, a throwaway directory, a fake clock and a local stand-in for adventofcode.com.
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    answers::Answers,
+    calendar::{Clock, SystemClock},
+    config::Config,
+    Part, Solver,
+};
+
+/// A fresh, empty directory under the system temp dir.
+pub fn temp_dir(name: &str) -> PathBuf {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let id = COUNTER.fetch_add(1, Ordering::SeqCst);
+    let path = std::env::temp_dir().join(format!("aoc-{name}-{}-{id}", std::process::id()));
+
+    let _ = std::fs::remove_dir_all(&path);
+    std::fs::create_dir_all(&path).unwrap();
+    path
+}
+
+/// A clock that only moves when slept on.
+pub struct FakeClock(Mutex<SystemTime>);
+
+impl FakeClock {
+    pub fn at(secs: u64) -> Self {
+        Self(Mutex::new(UNIX_EPOCH + Duration::from_secs(secs)))
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> SystemTime {
+        *self.0.lock().unwrap()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        *self.0.lock().unwrap() += duration;
+    }
+}
+
+/// Check a solution against your own input and its answer in `answers.toml`.
+///
+/// Inputs are private, so a fresh clone doesn't have any. Rather than fetch them this skips, saying so, unless
+/// `AOC_ALLOW_NETWORK` is set. A missing answer skips too.
+pub fn verify_answer(solver: &dyn Solver, part: Part) {
+    dotenvy::dotenv().ok();
+
+    let config = Config::from_env().unwrap();
+    let (year, day) = (solver.year(), solver.day());
+    let label = crate::solution::label(year, day, part);
+
+    let input_path = config.input_path(year, day);
+    if !input_path.is_file() && std::env::var_os("AOC_ALLOW_NETWORK").is_none() {
+        eprintln!("skipping {label}: no input at {}, set AOC_ALLOW_NETWORK=1 to fetch it", input_path.display());
+        return;
+    }
+
+    let answers = Answers::open(config.answers_path()).unwrap();
+    let Some(expected) = answers.get(year, day, part) else {
+        eprintln!("skipping {label}: no answer in {}", config.answers_path().display());
+        return;
+    };
+
+    let input = crate::get_input_with(&config, &SystemClock, year, day).unwrap();
+    let answer = solver.solve(solver.parse(&input).unwrap().as_ref(), part).unwrap();
+    assert_eq!(answer, expected, "{label}");
+}
+
+/// Check a solution against the stored examples for `part`, see [`crate::examples::verify`].
+///
+/// Examples only exist once the puzzle has been fetched, and those for part two once it has unlocked, so having none
+/// skips, saying so.
+pub fn verify_examples(solver: &dyn Solver, part: Part) {
+    if crate::examples::verify(solver, part).unwrap() == 0 {
+        let label = crate::solution::label(solver.year(), solver.day(), part);
+        eprintln!("skipping {label}: no examples, fetch the puzzle with `aoc read` to store them");
+    }
+}
+
+/// A request received by the [`StandIn`].
+#[derive(Debug, Clone)]
+pub struct Recorded {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Recorded {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter().find(|(key, _)| key.eq_ignore_ascii_case(name)).map(|(_, value)| value.as_str())
+    }
+}
+
+/// A tiny HTTP server on localhost that answers each connection with the next canned response.
+pub struct StandIn {
+    pub url: String,
+    requests: Arc<Mutex<Vec<Recorded>>>,
+}
+
+impl StandIn {
+    pub fn serve(responses: Vec<(u16, &str)>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let responses = responses.into_iter().map(|(status, body)| (status, body.to_string())).collect::<Vec<_>>();
+        let recorded = requests.clone();
+
+        std::thread::spawn(move || {
+            for (status, body) in responses {
+                let Ok((stream, _)) = listener.accept() else { return };
+                let mut reader = BufReader::new(stream);
+
+                // request line, then headers until a blank line
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let mut request_line = line.split_whitespace();
+                let method = request_line.next().unwrap_or_default().to_string();
+                let path = request_line.next().unwrap_or_default().to_string();
+
+                let mut headers = Vec::new();
+                loop {
+                    line.clear();
+                    reader.read_line(&mut line).unwrap();
+                    match line.trim_end().split_once(": ") {
+                        Some((key, value)) => headers.push((key.to_string(), value.to_string())),
+                        None => break,
+                    }
+                }
+
+                let length = headers
+                    .iter()
+                    .find(|(key, _)| key.eq_ignore_ascii_case("content-length"))
+                    .map(|(_, value)| value.parse().unwrap())
+                    .unwrap_or(0);
+                let mut buffer = vec![0; length];
+                reader.read_exact(&mut buffer).unwrap();
+
+                recorded.lock().unwrap().push(Recorded {
+                    method,
+                    path,
+                    headers,
+                    body: String::from_utf8_lossy(&buffer).to_string(),
+                });
+
+                let mut stream = reader.into_inner();
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} STAND-IN\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+
+        Self { url, requests }
+    }
+
+    /// Config pointing at this stand-in, without any throttling and with empty input and puzzle dirs.
+    pub fn config(&self) -> crate::config::Config {
+        crate::config::Config {
+            base_url: self.url.clone(),
+            token: Some("token".to_string()),
+            min_interval: Duration::ZERO,
+            input_dir: temp_dir("input"),
+            puzzle_dir: temp_dir("puzzle"),
+            examples_dir: temp_dir("examples"),
+            ..Default::default()
+        }
+    }
+
+    pub fn requests(&self) -> Vec<Recorded> {
+        self.requests.lock().unwrap().clone()
+    }
+}
@@ -1,18 +1,19 @@
-use eyre::OptionExt;
+use crate::{
+    parsing::{self, ParseError},
+    Answer, Pair,
+};
 
-use crate::Pair;
+pub fn parse(input: &str) -> crate::Result<Pair<Vec<u64>>> {
+    // parse the values on each line
+    let pairs = parsing::parse_lines_with(input, |line| {
+        let (l, r) = line.split_once("   ").ok_or_else(|| {
+            ParseError::new(line, format!("expected two numbers split by three spaces, found `{line}`"))
+        })?;
+        Ok((parsing::value::<u64>(l)?, parsing::value::<u64>(r)?))
+    })?;
 
-pub fn get_input() -> crate::Result<Pair<Vec<u64>>> {
     // make two lists
-    let mut left = Vec::new();
-    let mut right = Vec::new();
-
-    // for each line, parse the values
-    for line in crate::get_input(2024, 1)?.lines() {
-        let (l, r) = line.split_once("   ").ok_or_eyre("malformed line: `{line}`")?;
-        left.push(l.parse()?);
-        right.push(r.parse()?);
-    }
+    let (mut left, mut right): (Vec<_>, Vec<_>) = pairs.into_iter().unzip();
 
     // sort the lists
     left.sort_unstable();
@@ -22,11 +23,15 @@ pub fn get_input() -> crate::Result<Pair<Vec<u64>>> {
     Ok(Pair { left, right })
 }
 
-pub fn part_one(input: &Pair<Vec<u64>>) -> u64 {
-    input.left.iter().zip(input.right.iter()).map(|(a, b)| a.abs_diff(*b)).sum()
+pub fn get_input() -> crate::Result<Pair<Vec<u64>>> {
+    parse(&crate::get_input(2024, 1)?)
+}
+
+pub fn part_one(input: &Pair<Vec<u64>>) -> crate::Result<Answer> {
+    Ok(input.left.iter().zip(input.right.iter()).map(|(a, b)| a.abs_diff(*b)).sum::<u64>().into())
 }
 
-pub fn part_two(input: &Pair<Vec<u64>>) -> u64 {
+pub fn part_two(input: &Pair<Vec<u64>>) -> crate::Result<Answer> {
     // count number of occurrences in the left list
     let counts = input.left.iter().fold(std::collections::HashMap::new(), |mut acc, v| {
         *acc.entry(*v).or_insert(0) += 1;
@@ -34,16 +39,33 @@ pub fn part_two(input: &Pair<Vec<u64>>) -> u64 {
     });
 
     // for each number in the right list, if it exists in the left, multiply the two
-    input.right.iter().map(|num| if let Some(count) = counts.get(num) { count * (num) } else { 0 }).sum()
+    Ok(input
+        .right
+        .iter()
+        .map(|num| if let Some(count) = counts.get(num) { count * (num) } else { 0 })
+        .sum::<u64>()
+        .into())
 }
 
-pub fn main() -> crate::Result<()> {
-    let input = get_input()?;
+pub struct Day;
 
-    tracing::info!("y2024d01p01: {}", part_one(&input));
-    tracing::info!("y2024d01p02: {}", part_two(&input));
+impl crate::Solution for Day {
+    const YEAR: u64 = 2024;
+    const DAY: u64 = 1;
+
+    type Input = Pair<Vec<u64>>;
+
+    fn parse(input: &str) -> crate::Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part_one(input: &Self::Input) -> crate::Result<Answer> {
+        part_one(input)
+    }
 
-    Ok(())
+    fn part_two(input: &Self::Input) -> crate::Result<Answer> {
+        part_two(input)
+    }
 }
 
 #[cfg(test)]
@@ -52,13 +74,27 @@ mod tests {
 
     #[test]
     fn y2024d01p01() {
-        let input = get_input().unwrap();
-        assert_eq!(part_one(&input), 3_714_264);
+        crate::testing::verify_answer(&Day, crate::Part::One);
     }
 
     #[test]
     fn y2024d01p02() {
-        let input = get_input().unwrap();
-        assert_eq!(part_two(&input), 18_805_872);
+        crate::testing::verify_answer(&Day, crate::Part::Two);
+    }
+
+    #[test]
+    fn malformed_line() {
+        let error = crate::Solver::parse(&Day, "3   4\n4 3\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 2, column 1: expected two numbers split by three spaces, found `4 3`");
+    }
+
+    #[test]
+    fn y2024d01p01_examples() {
+        crate::testing::verify_examples(&Day, crate::Part::One);
+    }
+
+    #[test]
+    fn y2024d01p02_examples() {
+        crate::testing::verify_examples(&Day, crate::Part::Two);
     }
 }
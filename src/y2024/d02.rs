@@ -1,9 +1,13 @@
+use crate::{parsing, Answer};
+
+pub fn parse(input: &str) -> crate::Result<Vec<Vec<u64>>> {
+    parsing::parse_lines_with(input, |line| {
+        Ok(line.split_ascii_whitespace().map(parsing::value).collect::<Result<Vec<_>, _>>()?)
+    })
+}
+
 pub fn get_input() -> crate::Result<Vec<Vec<u64>>> {
-    crate::get_input(2024, 2)?
-        .trim()
-        .lines()
-        .map(|line| line.split_ascii_whitespace().map(|number| Ok(number.parse()?)).collect::<crate::Result<Vec<_>>>())
-        .collect::<crate::Result<Vec<_>>>()
+    parse(&crate::get_input(2024, 2)?)
 }
 
 fn check_report(report: &[u64]) -> bool {
@@ -14,12 +18,12 @@ fn check_report(report: &[u64]) -> bool {
     (rising || falling) && diffs_are_ok
 }
 
-pub fn part_one(input: &[Vec<u64>]) -> u64 {
-    input.iter().filter(|report| check_report(report)).count() as _
+pub fn part_one(input: &[Vec<u64>]) -> crate::Result<Answer> {
+    Ok(input.iter().filter(|report| check_report(report)).count().into())
 }
 
-pub fn part_two(input: &[Vec<u64>]) -> u64 {
-    input
+pub fn part_two(input: &[Vec<u64>]) -> crate::Result<Answer> {
+    let safe = input
         .iter()
         .filter(|&report| {
             // if this report is already safe, skip further checks
@@ -42,16 +46,30 @@ pub fn part_two(input: &[Vec<u64>]) -> u64 {
             // no checks passed, fail this report
             false
         })
-        .count() as _
+        .count();
+
+    Ok(safe.into())
 }
 
-pub fn main() -> crate::Result<()> {
-    let input = get_input()?;
+pub struct Day;
+
+impl crate::Solution for Day {
+    const YEAR: u64 = 2024;
+    const DAY: u64 = 2;
+
+    type Input = Vec<Vec<u64>>;
+
+    fn parse(input: &str) -> crate::Result<Self::Input> {
+        parse(input)
+    }
 
-    tracing::info!("y2024d02p01: {}", part_one(&input));
-    tracing::info!("y2024d02p02: {}", part_two(&input));
+    fn part_one(input: &Self::Input) -> crate::Result<Answer> {
+        part_one(input)
+    }
 
-    Ok(())
+    fn part_two(input: &Self::Input) -> crate::Result<Answer> {
+        part_two(input)
+    }
 }
 
 #[cfg(test)]
@@ -59,14 +77,22 @@ mod tests {
     use super::*;
 
     #[test]
-    fn y2024d01p01() {
-        let input = get_input().unwrap();
-        assert_eq!(part_one(&input), 631);
+    fn y2024d02p01() {
+        crate::testing::verify_answer(&Day, crate::Part::One);
+    }
+
+    #[test]
+    fn y2024d02p02() {
+        crate::testing::verify_answer(&Day, crate::Part::Two);
+    }
+
+    #[test]
+    fn y2024d02p01_examples() {
+        crate::testing::verify_examples(&Day, crate::Part::One);
     }
 
     #[test]
-    fn y2024d01p02() {
-        let input = get_input().unwrap();
-        assert_eq!(part_two(&input), 665);
+    fn y2024d02p02_examples() {
+        crate::testing::verify_examples(&Day, crate::Part::Two);
     }
 }
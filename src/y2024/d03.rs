@@ -1,17 +1,17 @@
 use eyre::OptionExt;
 
+use crate::Answer;
+
 pub enum Instruction {
     Enable,
     Disable,
     Mul(u64, u64),
 }
 
-pub fn get_input() -> crate::Result<Vec<Instruction>> {
-    let input = crate::get_input(2024, 3)?;
-
+pub fn parse(input: &str) -> crate::Result<Vec<Instruction>> {
     // already on the regex
     let re = regex::Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)|do\(\)|don't\(\)")?;
-    re.captures_iter(&input)
+    re.captures_iter(input)
         .map(|match_| {
             let capture = match_.get(0).ok_or_eyre("bad match")?.as_str();
             let (op, _) = capture.split_once("(").ok_or_eyre("bad op")?;
@@ -33,11 +33,15 @@ pub fn get_input() -> crate::Result<Vec<Instruction>> {
         .collect::<crate::Result<Vec<_>>>()
 }
 
-pub fn part_one(input: &[Instruction]) -> u64 {
-    input.iter().map(|op| if let Instruction::Mul(a, b) = op { a * b } else { 0 }).sum()
+pub fn get_input() -> crate::Result<Vec<Instruction>> {
+    parse(&crate::get_input(2024, 3)?)
+}
+
+pub fn part_one(input: &[Instruction]) -> crate::Result<Answer> {
+    Ok(input.iter().map(|op| if let Instruction::Mul(a, b) = op { a * b } else { 0 }).sum::<u64>().into())
 }
 
-pub fn part_two(input: &[Instruction]) -> u64 {
+pub fn part_two(input: &[Instruction]) -> crate::Result<Answer> {
     let mut count = 0;
     let mut enabled = true;
 
@@ -52,16 +56,29 @@ pub fn part_two(input: &[Instruction]) -> u64 {
             }
         }
     }
-    count
+
+    Ok(count.into())
 }
 
-pub fn main() -> crate::Result<()> {
-    let input = get_input()?;
+pub struct Day;
+
+impl crate::Solution for Day {
+    const YEAR: u64 = 2024;
+    const DAY: u64 = 3;
 
-    tracing::info!("y2024d03p01: {}", part_one(&input));
-    tracing::info!("y2024d03p02: {}", part_two(&input));
+    type Input = Vec<Instruction>;
+
+    fn parse(input: &str) -> crate::Result<Self::Input> {
+        parse(input)
+    }
 
-    Ok(())
+    fn part_one(input: &Self::Input) -> crate::Result<Answer> {
+        part_one(input)
+    }
+
+    fn part_two(input: &Self::Input) -> crate::Result<Answer> {
+        part_two(input)
+    }
 }
 
 #[cfg(test)]
@@ -70,13 +87,21 @@ mod tests {
 
     #[test]
     fn y2024d03p01() {
-        let input = get_input().unwrap();
-        assert_eq!(part_one(&input), 153_469_856);
+        crate::testing::verify_answer(&Day, crate::Part::One);
     }
 
     #[test]
     fn y2024d03p02() {
-        let input = get_input().unwrap();
-        assert_eq!(part_two(&input), 77_055_967);
+        crate::testing::verify_answer(&Day, crate::Part::Two);
+    }
+
+    #[test]
+    fn y2024d03p01_examples() {
+        crate::testing::verify_examples(&Day, crate::Part::One);
+    }
+
+    #[test]
+    fn y2024d03p02_examples() {
+        crate::testing::verify_examples(&Day, crate::Part::Two);
     }
 }
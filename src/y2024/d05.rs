@@ -1,107 +1,86 @@
-use std::collections::{HashMap, HashSet};
-
-use eyre::OptionExt;
-
-type Rules = HashMap<u64, HashSet<u64>>;
+use crate::{
+    order::PartialOrder,
+    parsing::{self, ParseError},
+    Answer,
+};
 
 pub struct Input {
-    pub rules: Rules,
+    pub rules: PartialOrder<u64>,
     pub updates: Vec<Vec<u64>>,
 }
 
-fn is_correct(update: &[u64], rules: &Rules) -> bool {
-    for (key, values) in rules {
-        for value in values {
-            if update.contains(key) && update.contains(value) {
-                let idx_left = update.iter().position(|v| v == key).unwrap_or(usize::MAX);
-                let idx_right = update.iter().position(|v| v == value).unwrap_or(usize::MIN);
-
-                if idx_left >= idx_right {
-                    return false;
-                }
-            }
-        }
-    }
-
-    true
-}
-
-pub fn get_input() -> crate::Result<Input> {
-    let input = crate::get_input(2024, 5)?;
-    let (rules_raw, updates_raw) = input.split_once("\n\n").ok_or_eyre(r"Unable to split input at \n\n")?;
-
-    let mut rules: Rules = HashMap::new();
+pub fn parse(input: &str) -> crate::Result<Input> {
+    let mut sections = parsing::sections(input);
+    let (Some(rules_raw), Some(updates_raw)) = (sections.next(), sections.next()) else {
+        eyre::bail!("expected rules and updates separated by a blank line");
+    };
 
-    for rule in rules_raw.trim().lines() {
-        let (left, right) = rule.split_once('|').ok_or_eyre("unable to split rule on |")?;
-        let (left, right) = (left.parse()?, right.parse()?);
-        rules.entry(left).or_default().insert(right);
-    }
+    let rules = parsing::parse_lines_with(rules_raw, |rule| {
+        let (left, right) =
+            rule.split_once('|').ok_or_else(|| ParseError::new(rule, "expected a rule like `47|53`"))?;
+        Ok((parsing::value(left)?, parsing::value(right)?))
+    })?
+    .into_iter()
+    .collect::<PartialOrder<_>>();
 
-    let updates = updates_raw
-        .trim()
-        .lines()
-        .map(|line| line.split(',').map(|value| Ok(value.parse()?)).collect::<crate::Result<Vec<_>>>())
-        .collect::<crate::Result<Vec<Vec<_>>>>()?;
+    let updates = parsing::parse_lines_with(updates_raw, |line| {
+        Ok(line.split(',').map(parsing::value).collect::<Result<Vec<_>, _>>()?)
+    })?;
 
     Ok(Input { rules, updates })
 }
 
-pub fn part_one(input: &Input) -> u64 {
+pub fn get_input() -> crate::Result<Input> {
+    parse(&crate::get_input(2024, 5)?)
+}
+
+pub fn part_one(input: &Input) -> crate::Result<Answer> {
     let mut count = 0;
 
     for update in &input.updates {
-        if is_correct(update, &input.rules) {
+        if input.rules.is_consistent(update) {
             count += update[update.len() / 2];
         }
     }
 
-    count
+    Ok(count.into())
 }
 
-pub fn part_two(input: &Input) -> u64 {
+pub fn part_two(input: &Input) -> crate::Result<Answer> {
     let mut count = 0;
 
-    for update in &input.updates {
-        if !is_correct(update, &input.rules) {
-            // repeatedly apply the rules until this rule is correct
-            let mut update = update.clone();
-
-            loop {
-                let mut changed = false;
-                for (key, values) in &input.rules {
-                    for value in values {
-                        if update.contains(key) && update.contains(value) {
-                            let idx_left = update.iter().position(|v| v == key).unwrap_or(usize::MAX);
-                            let idx_right = update.iter().position(|v| v == value).unwrap_or(usize::MIN);
-
-                            if idx_left >= idx_right {
-                                update.swap(idx_left, idx_right);
-                                changed = true
-                            }
-                        }
-                    }
-                }
-
-                // every rule passed, this update is now correct
-                if !changed {
-                    break;
-                }
-            }
+    for (index, update) in input.updates.iter().enumerate() {
+        if let Some((before, after)) = input.rules.first_violation(update) {
+            tracing::debug!("update {} breaks the rule {before}|{after}", index + 1);
+
+            // put the pages in an order that follows every rule
+            let update = input.rules.sort(update)?;
             count += update[update.len() / 2];
         }
     }
 
-    count
+    Ok(count.into())
 }
 
-pub fn main() -> crate::Result<()> {
-    let input = get_input()?;
+pub struct Day;
+
+impl crate::Solution for Day {
+    const YEAR: u64 = 2024;
+    const DAY: u64 = 5;
 
-    tracing::info!("y2024d05p01: {}", part_one(&input));
-    tracing::info!("y2024d05p02: {}", part_two(&input));
+    type Input = Input;
 
-    Ok(())
+    fn parse(input: &str) -> crate::Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part_one(input: &Self::Input) -> crate::Result<Answer> {
+        part_one(input)
+    }
+
+    fn part_two(input: &Self::Input) -> crate::Result<Answer> {
+        part_two(input)
+    }
 }
 
 #[cfg(test)]
@@ -110,13 +89,31 @@ mod tests {
 
     #[test]
     fn y2024d05p01() {
-        let input = get_input().unwrap();
-        assert_eq!(part_one(&input), 6_242);
+        crate::testing::verify_answer(&Day, crate::Part::One);
     }
 
     #[test]
     fn y2024d05p02() {
-        let input = get_input().unwrap();
-        assert_eq!(part_two(&input), 5_169);
+        crate::testing::verify_answer(&Day, crate::Part::Two);
+    }
+
+    #[test]
+    fn broken_rules() {
+        let examples = crate::examples::load(&crate::config::Config::from_env().unwrap(), 2024, 5).unwrap();
+        let input = parse(&examples[0].input).unwrap();
+
+        assert_eq!(input.rules.first_violation(&input.updates[0]), None);
+        assert_eq!(input.rules.first_violation(&input.updates[3]), Some((97, 75)));
+        assert_eq!(input.rules.sort(&input.updates[3]).unwrap(), vec![97, 75, 47, 61, 53]);
+    }
+
+    #[test]
+    fn y2024d05p01_examples() {
+        crate::testing::verify_examples(&Day, crate::Part::One);
+    }
+
+    #[test]
+    fn y2024d05p02_examples() {
+        crate::testing::verify_examples(&Day, crate::Part::Two);
     }
 }
@@ -1,26 +1,23 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 
 use eyre::OptionExt;
 use itertools::Itertools;
 use rayon::prelude::*;
 
-pub type Position = (i64, i64);
-
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub enum Direction {
-    North,
-    East,
-    South,
-    West,
-}
+use crate::{
+    cycle::Period,
+    grid::Grid,
+    point::{Direction, Point},
+    Answer,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Guard {
-    pub position: Position,
+    pub position: Point,
     pub direction: Direction,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Tile {
     Floor,
     Wall,
@@ -36,136 +33,307 @@ impl From<char> for Tile {
 }
 
 impl Guard {
-    fn next(&self) -> Position {
-        match self.direction {
-            Direction::North => (self.position.0, self.position.1 - 1),
-            Direction::East => (self.position.0 + 1, self.position.1),
-            Direction::South => (self.position.0, self.position.1 + 1),
-            Direction::West => (self.position.0 - 1, self.position.1),
-        }
+    fn next(&self) -> Point {
+        self.position.step(self.direction)
     }
 
-    fn turn(&self) -> Direction {
-        // turn right 90 degrees
-        match self.direction {
-            Direction::North => Direction::East,
-            Direction::East => Direction::South,
-            Direction::South => Direction::West,
-            Direction::West => Direction::North,
+    /// Step forwards, or turn right if there's a wall in the way. `None` once the guard walks off the map.
+    fn step(&self, tiles: &Grid<Tile>) -> Option<Guard> {
+        let next = self.next();
+
+        match tiles.get(next)? {
+            Tile::Floor => Some(Guard { position: next, direction: self.direction }),
+            Tile::Wall => Some(Guard { position: self.position, direction: self.direction.turn_right() }),
         }
     }
 }
 
-pub type Input = (HashMap<Position, Tile>, Guard);
-
-pub fn get_input() -> crate::Result<Input> {
-    let input = crate::get_input(2024, 6)?;
+pub type Input = (Grid<Tile>, Guard);
 
-    // setup input params
-    let mut tiles = HashMap::new();
-    let mut guard = None;
+pub fn parse(input: &str) -> crate::Result<Input> {
+    let map = Grid::parse(input, |ch| ch)?;
 
-    for (y, line) in input.lines().enumerate() {
-        for (x, char) in line.char_indices() {
-            tiles.insert((x as _, y as _), Tile::from(char));
+    let position = map.find(&'^').ok_or_eyre("There isn't a guard on the map, can't build input.")?;
+    let guard = Guard { position, direction: Direction::North };
 
-            if char == '^' {
-                guard = Some(Guard { position: (x as _, y as _), direction: Direction::North })
-            }
-        }
-    }
+    Ok((map.map(|&ch| Tile::from(ch)), guard))
+}
 
-    let guard = guard.ok_or_eyre("There isn't a guard on the map, can't build input.")?;
-    Ok((tiles, guard))
+pub fn get_input() -> crate::Result<Input> {
+    parse(&crate::get_input(2024, 6)?)
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SimulationResult {
     OutOfBounds(u64),
     InfiniteLoop,
 }
 
-pub fn run_simulation(tiles: &HashMap<Position, Tile>, guard: &Guard) -> SimulationResult {
+pub fn run_simulation(tiles: &Grid<Tile>, guard: &Guard) -> SimulationResult {
     let mut visited = HashSet::new();
-    let mut obstructions = HashSet::new();
-
-    let mut guard = guard.clone();
 
-    loop {
-        // mark this tile as visited
+    // the guard is in a loop once they're back somewhere, facing the same way
+    let cycle = crate::cycle::find_cycle(guard.clone(), |guard| {
         visited.insert(guard.position);
+        guard.step(tiles)
+    });
 
-        // look ahead
-        let next_position = guard.next();
-
-        // and do something based on what is there
-        match tiles.get(&next_position) {
-            None => return SimulationResult::OutOfBounds(visited.len() as _), // not on the map any more, stop
-            Some(tile) => match tile {
-                Tile::Floor => guard.position = next_position,
-                Tile::Wall => {
-                    // have we been blocked in this direction before? Then we're in a loop, stop.
-                    if obstructions.contains(&(next_position, guard.direction.clone())) {
-                        return SimulationResult::InfiniteLoop;
-                    }
-
-                    // otherwise mark this obstruction and turn
-                    obstructions.insert((next_position, guard.direction.clone()));
-                    guard.direction = guard.turn();
+    match cycle {
+        Some(_) => SimulationResult::InfiniteLoop,
+        None => SimulationResult::OutOfBounds(visited.len() as _), // not on the map any more
+    }
+}
+
+/// For every cell and heading, where the guard stops in front of the next wall, `None` if they walk off the map first.
+pub struct Jumps {
+    stops: Grid<[Option<Point>; 4]>,
+}
+
+/// Which of a cell's four stops belongs to `direction`, the cardinal directions are every other one.
+fn heading(direction: Direction) -> usize {
+    direction as usize / 2
+}
+
+impl Jumps {
+    pub fn new(tiles: &Grid<Tile>) -> Self {
+        let mut stops = tiles.map(|_| [None; 4]);
+        let positions = tiles.positions().collect_vec();
+
+        for direction in Direction::CARDINAL {
+            // fill in the cell ahead before the cell behind it, so each stop is one lookup away
+            let order = match direction {
+                Direction::North | Direction::West => positions.clone(),
+                _ => positions.iter().rev().copied().collect(),
+            };
+
+            for position in order {
+                let next = position.step(direction);
+                let stop = match tiles.get(next) {
+                    None => None,
+                    Some(Tile::Wall) => Some(position),
+                    Some(Tile::Floor) => stops.get(next).and_then(|stops| stops[heading(direction)]),
+                };
+
+                if let Some(stops) = stops.get_mut(position) {
+                    stops[heading(direction)] = stop;
                 }
-            },
+            }
         }
+
+        Self { stops }
     }
-}
 
-pub fn part_one(input: &Input) -> u64 {
-    let (tiles, guard) = input.clone();
+    /// Where the guard stops walking from `position` towards `direction` with an extra wall at `obstruction`.
+    fn stop(&self, position: Point, direction: Direction, obstruction: Point) -> Option<Point> {
+        let stop = self.stops.get(position).and_then(|stops| stops[heading(direction)]);
 
-    match run_simulation(&tiles, &guard) {
-        SimulationResult::OutOfBounds(value) => value,
-        SimulationResult::InfiniteLoop => 0,
+        // how many steps ahead the obstruction is, if it's straight ahead at all
+        let unit = direction.unit();
+        let offset = obstruction - position;
+        let ahead = offset.x * unit.x + offset.y * unit.y;
+        let in_line = ahead > 0 && offset == unit * ahead;
+
+        let blocked =
+            in_line && stop.is_none_or(|stop| ahead <= (stop - position).x * unit.x + (stop - position).y * unit.y);
+
+        if blocked {
+            Some(position + unit * (ahead - 1))
+        } else {
+            stop
+        }
+    }
+
+    /// Whether the guard walks in circles once `obstruction` is added, jumping from wall to wall.
+    pub fn loops(&self, guard: &Guard, obstruction: Point) -> bool {
+        crate::cycle::find_cycle(guard.clone(), |guard| {
+            let stop = self.stop(guard.position, guard.direction, obstruction)?;
+            Some(Guard { position: stop, direction: guard.direction.turn_right() })
+        })
+        .is_some()
     }
 }
 
-pub fn part_two(input: &Input) -> u64 {
-    // find the bounds of the map
-    let (tiles, guard) = input.clone();
-    let (x_max, y_max) = tiles.keys().cloned().sorted().next_back().unwrap();
+/// Every cell where one more wall sends the guard into a loop, in the order the guard first reaches them.
+///
+/// Only cells on the guard's path can change where they go. Each is tried from where the guard is just before they
+/// first walk into it, since everything up to there happens the same way.
+pub fn loop_obstructions(tiles: &Grid<Tile>, guard: &Guard) -> Vec<Point> {
+    let mut visited = HashSet::from([guard.position]);
+    let mut candidates = Vec::new();
+    let mut current = guard.clone();
+
+    while let Some(next) = current.step(tiles) {
+        if next.position != current.position && visited.insert(next.position) {
+            candidates.push((current.clone(), next.position));
+        }
+        current = next;
+    }
+
+    let jumps = Jumps::new(tiles);
+    candidates
+        .par_iter()
+        .filter_map(|(guard, obstruction)| jumps.loops(guard, *obstruction).then_some(*obstruction))
+        .collect()
+}
+
+/// Every state the guard was in, from the start until they walk off the map or would start repeating themselves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path {
+    pub states: Vec<Guard>,
+    /// Where the states repeat, when the guard is stuck in a loop.
+    pub cycle: Option<Period>,
+}
 
-    let mut obstructions = Vec::new();
+/// Like [`run_simulation`], keeping the guard's path so it can be drawn.
+pub fn record_simulation(tiles: &Grid<Tile>, guard: &Guard) -> Path {
+    let (states, cycle) = crate::cycle::trace(guard.clone(), |guard| guard.step(tiles));
+    Path { states, cycle }
+}
 
-    for y in 0..=y_max {
-        for x in 0..=x_max {
-            obstructions.push((x, y));
+impl Path {
+    pub fn result(&self) -> SimulationResult {
+        match self.cycle {
+            Some(_) => SimulationResult::InfiniteLoop,
+            None => SimulationResult::OutOfBounds(self.visited().len() as _),
         }
     }
 
-    obstructions
-        .par_iter()
-        .map(|(x, y)| {
-            if let Some(Tile::Wall) = tiles.get(&(*x, *y)) {
-                return 0; // this position is already obstructed, skip
+    pub fn visited(&self) -> HashSet<Point> {
+        self.states.iter().map(|guard| guard.position).collect()
+    }
+
+    /// Where the guard turned, in the order they did. Turning around only counts once.
+    pub fn turns(&self) -> Vec<Point> {
+        self.states
+            .iter()
+            .tuple_windows()
+            .filter(|(before, after)| before.position == after.position)
+            .map(|(before, _)| before.position)
+            .dedup()
+            .collect()
+    }
+
+    /// Draw the path on the map like the puzzle does, with `|` and `-` where the guard walked, `+` where they turned
+    /// or crossed their own path, and an `O` at `obstruction` if there is one.
+    pub fn render(&self, tiles: &Grid<Tile>, obstruction: Option<Point>) -> String {
+        self.render_until(tiles, obstruction, self.states.len())
+    }
+
+    /// A drawing for every state along the path, to watch the guard go.
+    pub fn frames<'a>(
+        &'a self,
+        tiles: &'a Grid<Tile>,
+        obstruction: Option<Point>,
+    ) -> impl Iterator<Item = String> + 'a {
+        (1..=self.states.len()).map(move |steps| self.render_until(tiles, obstruction, steps))
+    }
+
+    fn render_until(&self, tiles: &Grid<Tile>, obstruction: Option<Point>, steps: usize) -> String {
+        let states = &self.states[..steps.min(self.states.len())];
+
+        // a loop closes by stepping from the last state back to where it started repeating
+        let closing = self
+            .cycle
+            .filter(|_| states.len() == self.states.len())
+            .and_then(|period| Some((states.last()?, &self.states[period.start])));
+
+        // which ways the guard walked through each cell, up and down then side to side
+        let mut walked = tiles.map(|_| (false, false));
+        for (before, after) in states.iter().tuple_windows().chain(closing) {
+            if before.position == after.position {
+                continue;
             }
 
-            // otherwise build a new map with an obstruction at this location
-            let mut tiles = tiles.clone();
-            tiles.insert((*x, *y), Tile::Wall);
+            let vertical = matches!(before.direction, Direction::North | Direction::South);
+            for position in [before.position, after.position] {
+                if let Some((up_and_down, side_to_side)) = walked.get_mut(position) {
+                    *up_and_down |= vertical;
+                    *side_to_side |= !vertical;
+                }
+            }
+        }
 
-            // run and count the infinite loops
-            match run_simulation(&tiles, &guard) {
-                SimulationResult::InfiniteLoop => 1,
-                _ => 0,
+        let turns = Path { states: states.to_vec(), cycle: None }.turns();
+        for turn in turns {
+            if let Some(cell) = walked.get_mut(turn) {
+                *cell = (true, true);
             }
-        })
-        .sum()
+        }
+
+        let mut drawing = tiles.map(|tile| match tile {
+            Tile::Wall => '#',
+            Tile::Floor => '.',
+        });
+
+        for (position, cell) in walked.iter() {
+            let ch = match cell {
+                (true, true) => '+',
+                (true, false) => '|',
+                (false, true) => '-',
+                (false, false) => continue,
+            };
+            if let Some(drawn) = drawing.get_mut(position) {
+                *drawn = ch;
+            }
+        }
+
+        if let Some(start) = self.states.first() {
+            if let Some(drawn) = drawing.get_mut(start.position) {
+                *drawn = match start.direction {
+                    Direction::East => '>',
+                    Direction::South => 'v',
+                    Direction::West => '<',
+                    _ => '^',
+                };
+            }
+        }
+
+        if let Some(drawn) = obstruction.and_then(|obstruction| drawing.get_mut(obstruction)) {
+            *drawn = 'O';
+        }
+
+        drawing.to_string()
+    }
 }
 
-pub fn main() -> crate::Result<()> {
-    let input = get_input()?;
+pub fn part_one(input: &Input) -> crate::Result<Answer> {
+    let (tiles, guard) = input.clone();
+
+    // recording is slower, only draw the path when someone's looking
+    if tracing::enabled!(tracing::Level::DEBUG) {
+        tracing::debug!("the guard's path:\n{}", record_simulation(&tiles, &guard).render(&tiles, None));
+    }
 
-    tracing::info!("y2024d06p01: {}", part_one(&input));
-    tracing::info!("y2024d06p02: {}", part_two(&input));
+    match run_simulation(&tiles, &guard) {
+        SimulationResult::OutOfBounds(value) => Ok(value.into()),
+        SimulationResult::InfiniteLoop => eyre::bail!("the guard never leaves the map"),
+    }
+}
 
-    Ok(())
+pub fn part_two(input: &Input) -> crate::Result<Answer> {
+    let (tiles, guard) = input;
+    Ok(loop_obstructions(tiles, guard).len().into())
+}
+
+pub struct Day;
+
+impl crate::Solution for Day {
+    const YEAR: u64 = 2024;
+    const DAY: u64 = 6;
+
+    type Input = Input;
+
+    fn parse(input: &str) -> crate::Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part_one(input: &Self::Input) -> crate::Result<Answer> {
+        part_one(input)
+    }
+
+    fn part_two(input: &Self::Input) -> crate::Result<Answer> {
+        part_two(input)
+    }
 }
 
 #[cfg(test)]
@@ -174,13 +342,96 @@ mod tests {
 
     #[test]
     fn y2024d06p01() {
-        let input = get_input().unwrap();
-        assert_eq!(part_one(&input), 5_531);
+        crate::testing::verify_answer(&Day, crate::Part::One);
     }
 
     #[test]
     fn y2024d06p02() {
-        let input = get_input().unwrap();
-        assert_eq!(part_two(&input), 2_165);
+        crate::testing::verify_answer(&Day, crate::Part::Two);
+    }
+
+    fn example() -> Input {
+        let examples = crate::examples::load(&crate::config::Config::from_env().unwrap(), 2024, 6).unwrap();
+        parse(&examples[0].input).unwrap()
+    }
+
+    #[test]
+    fn record_path() {
+        let (tiles, guard) = example();
+        let path = record_simulation(&tiles, &guard);
+
+        assert_eq!(path.result(), SimulationResult::OutOfBounds(41));
+        assert_eq!(path.turns()[..3], [(4, 1), (8, 1), (8, 6)].map(Point::from));
+
+        let frames = path.frames(&tiles, None).collect_vec();
+        assert_eq!(frames.len(), path.states.len());
+        assert_eq!(frames.last(), Some(&path.render(&tiles, None)));
+    }
+
+    #[test]
+    fn render_loops() {
+        let (tiles, guard) = example();
+
+        // the first two ways of trapping the guard, drawn as they are in the puzzle
+        let drawings = [
+            (
+                Point::new(3, 6),
+                "\
+....#.....
+....+---+#
+....|...|.
+..#.|...|.
+....|..#|.
+....|...|.
+.#.O^---+.
+........#.
+#.........
+......#...
+",
+            ),
+            (
+                Point::new(6, 7),
+                "\
+....#.....
+....+---+#
+....|...|.
+..#.|...|.
+..+-+-+#|.
+..|.|.|.|.
+.#+-^-+-+.
+......O.#.
+#.........
+......#...
+",
+            ),
+        ];
+
+        for (obstruction, drawing) in drawings {
+            let mut blocked = tiles.clone();
+            *blocked.get_mut(obstruction).unwrap() = Tile::Wall;
+
+            let path = record_simulation(&blocked, &guard);
+            assert_eq!(path.result(), SimulationResult::InfiniteLoop);
+            assert_eq!(path.render(&tiles, Some(obstruction)), drawing);
+        }
+    }
+
+    #[test]
+    fn obstructions_on_the_path() {
+        let (tiles, guard) = example();
+
+        let positions = loop_obstructions(&tiles, &guard).into_iter().sorted().collect_vec();
+        let expected = [(1, 8), (3, 6), (3, 8), (6, 7), (7, 7), (7, 9)].map(Point::from);
+        assert_eq!(positions, expected);
+    }
+
+    #[test]
+    fn y2024d06p01_examples() {
+        crate::testing::verify_examples(&Day, crate::Part::One);
+    }
+
+    #[test]
+    fn y2024d06p02_examples() {
+        crate::testing::verify_examples(&Day, crate::Part::Two);
     }
 }
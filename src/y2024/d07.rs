@@ -1,79 +1,212 @@
+use std::fmt::Display;
+
 use eyre::OptionExt;
 use rayon::prelude::*;
 
-use crate::util::ProductRepeat;
+use crate::{
+    math,
+    parsing::{self, ParseError},
+    Answer,
+};
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Operation {
     Add,
     Mul,
     Concat,
 }
 
+impl Operation {
+    /// `a` combined with `b`, `None` if it overflows.
+    pub fn apply(self, a: u64, b: u64) -> Option<u64> {
+        match self {
+            Operation::Add => a.checked_add(b),
+            Operation::Mul => a.checked_mul(b),
+            Operation::Concat => math::checked_concat(a, b),
+        }
+    }
+
+    /// The `a` that `apply(a, b)` turns into `result`, if there is one.
+    ///
+    /// Anything times zero is zero, so there's no single `a` to give back then, see [`solve_backwards`].
+    fn undo(self, result: u64, b: u64) -> Option<u64> {
+        match self {
+            Operation::Add => result.checked_sub(b),
+            Operation::Mul => (b != 0 && result.is_multiple_of(b)).then(|| result / b),
+            Operation::Concat => math::strip_suffix(result, b),
+        }
+    }
+}
+
+impl Display for Operation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Operation::Add => write!(f, "+"),
+            Operation::Mul => write!(f, "*"),
+            Operation::Concat => write!(f, "||"),
+        }
+    }
+}
+
 pub type Equation = (u64, Vec<u64>);
 
+/// The values of an equation with an operator between each, evaluated left to right.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expression {
+    pub values: Vec<u64>,
+    pub operations: Vec<Operation>,
+}
+
+impl Expression {
+    pub fn evaluate(&self) -> crate::Result<u64> {
+        let (first, rest) = self.values.split_first().ok_or_eyre("the expression has no values")?;
+
+        rest.iter().zip(&self.operations).try_fold(*first, |total, (&value, operation)| {
+            operation.apply(total, value).ok_or_else(|| eyre::eyre!("`{self}` overflows"))
+        })
+    }
+}
+
+impl Display for Expression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, value) in self.values.iter().enumerate() {
+            if let Some(operation) = index.checked_sub(1).and_then(|before| self.operations.get(before)) {
+                write!(f, " {operation} ")?;
+            }
+            write!(f, "{value}")?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn parse(input: &str) -> crate::Result<Vec<Equation>> {
+    parsing::parse_lines_with(input, |line| {
+        let (target, values) =
+            line.split_once(": ").ok_or_else(|| ParseError::new(line, "expected an equation like `190: 10 19`"))?;
+        let values = values.split(' ').map(parsing::value).collect::<Result<Vec<_>, _>>()?;
+
+        Ok((parsing::value(target)?, values))
+    })
+}
+
 pub fn get_input() -> crate::Result<Vec<Equation>> {
-    crate::get_input(2024, 7)?
-        .trim()
-        .lines()
-        .map(|line| {
-            let (target, values) = line.split_once(": ").ok_or_eyre("Can't split equation")?;
-            let target = target.parse()?;
-            let values = values.split(' ').map(|value| Ok(value.parse()?)).collect::<crate::Result<Vec<_>>>()?;
-
-            Ok((target, values))
+    parse(&crate::get_input(2024, 7)?)
+}
+
+/// The operations that keep each running total of `values` smallest, `None` if even those overflow.
+///
+/// Every operation grows with the total it's applied to, so no other choice stays below these or avoids an overflow
+/// they run into.
+fn smallest_operations(values: &[u64], operations: &[Operation]) -> Option<Vec<Operation>> {
+    let (&first, rest) = values.split_first()?;
+    let mut total = first;
+
+    rest.iter()
+        .map(|&value| {
+            let (next, operation) = operations
+                .iter()
+                .filter_map(|&operation| Some((operation.apply(total, value)?, operation)))
+                .min_by_key(|&(next, _)| next)?;
+            total = next;
+            Some(operation)
         })
-        .collect::<crate::Result<Vec<_>>>()
-}
-
-pub fn can_be_solved(target: u64, values: &[u64], operations: &[Operation]) -> bool {
-    for permutation in operations.iter().product_repeat(values.len() - 1) {
-        let mut operators = permutation.iter();
-
-        let result = values
-            .iter()
-            .copied()
-            // SAFE: product_repeat guarantees enough operators based on the number of values
-            .reduce(|a, b| match operators.next().unwrap() {
-                Operation::Add => a + b,
-                Operation::Mul => a * b,
-                // SAFE: we know the inputs are unsigned ints and they'll always concat correctly
-                Operation::Concat => format!("{a}{b}").parse().unwrap(),
-            })
-            .unwrap();
-
-        if result == target {
+        .collect()
+}
+
+/// Peel values off the end of `values`, undoing each operation that could have produced `target`, until only the
+/// first is left and it has to be what remains. Pushes the operations used, last first.
+fn solve_backwards(target: u64, values: &[u64], operations: &[Operation], used: &mut Vec<Operation>) -> bool {
+    let Some((&last, rest)) = values.split_last() else {
+        return false;
+    };
+
+    if rest.is_empty() {
+        return target == last;
+    }
+
+    // multiplying by zero makes zero whatever came before, as long as combining the rest doesn't overflow
+    if target == 0 && last == 0 && operations.contains(&Operation::Mul) {
+        if let Some(before) = smallest_operations(rest, operations) {
+            used.push(Operation::Mul);
+            used.extend(before.into_iter().rev());
             return true;
         }
     }
 
+    for &operation in operations {
+        if let Some(before) = operation.undo(target, last) {
+            used.push(operation);
+            if solve_backwards(before, rest, operations, used) {
+                return true;
+            }
+            used.pop();
+        }
+    }
+
     false
 }
 
-pub fn part_one(input: &[Equation]) -> u64 {
-    input
-        .par_iter()
-        .filter_map(|(target, values)| {
-            can_be_solved(*target, values, &[Operation::Add, Operation::Mul]).then_some(target)
-        })
-        .sum()
+/// A way to put `operations` between `values` that makes `target`, if there is one.
+///
+/// Working backwards prunes most of the choices, a product has to divide the target and a concatenation has to end
+/// it.
+pub fn solve(target: u64, values: &[u64], operations: &[Operation]) -> Option<Expression> {
+    let mut used = Vec::with_capacity(values.len().saturating_sub(1));
+    if !solve_backwards(target, values, operations, &mut used) {
+        return None;
+    }
+
+    used.reverse();
+    Some(Expression { values: values.to_vec(), operations: used })
 }
 
-pub fn part_two(input: &[Equation]) -> u64 {
-    input
+/// The sum of the targets that can be made with `operations`.
+fn calibrate(input: &[Equation], operations: &[Operation]) -> crate::Result<Answer> {
+    let solved = input
         .par_iter()
         .filter_map(|(target, values)| {
-            can_be_solved(*target, values, &[Operation::Add, Operation::Mul, Operation::Concat]).then_some(target)
+            let expression = solve(*target, values, operations)?;
+            tracing::debug!("{expression} = {target}");
+            Some(*target)
         })
-        .sum()
+        .collect::<Vec<_>>();
+
+    let total = solved
+        .into_iter()
+        .try_fold(0u64, |total, target| total.checked_add(target))
+        .ok_or_eyre("the calibration total overflows")?;
+
+    Ok(total.into())
 }
 
-pub fn main() -> crate::Result<()> {
-    let input = get_input()?;
+pub fn part_one(input: &[Equation]) -> crate::Result<Answer> {
+    calibrate(input, &[Operation::Add, Operation::Mul])
+}
+
+pub fn part_two(input: &[Equation]) -> crate::Result<Answer> {
+    calibrate(input, &[Operation::Add, Operation::Mul, Operation::Concat])
+}
 
-    tracing::info!("y2024d07p01: {}", part_one(&input));
-    tracing::info!("y2024d07p02: {}", part_two(&input));
+pub struct Day;
 
-    Ok(())
+impl crate::Solution for Day {
+    const YEAR: u64 = 2024;
+    const DAY: u64 = 7;
+
+    type Input = Vec<Equation>;
+
+    fn parse(input: &str) -> crate::Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part_one(input: &Self::Input) -> crate::Result<Answer> {
+        part_one(input)
+    }
+
+    fn part_two(input: &Self::Input) -> crate::Result<Answer> {
+        part_two(input)
+    }
 }
 
 #[cfg(test)]
@@ -82,13 +215,71 @@ mod tests {
 
     #[test]
     fn y2024d07p01() {
-        let input = get_input().unwrap();
-        assert_eq!(part_one(&input), 3_245_122_495_150);
+        crate::testing::verify_answer(&Day, crate::Part::One);
     }
 
     #[test]
     fn y2024d07p02() {
-        let input = get_input().unwrap();
-        assert_eq!(part_two(&input), 105_517_128_211_543);
+        crate::testing::verify_answer(&Day, crate::Part::Two);
+    }
+
+    #[test]
+    fn expressions() {
+        let expression = solve(3267, &[81, 40, 27], &[Operation::Add, Operation::Mul]).unwrap();
+        assert_eq!(expression.to_string(), "81 * 40 + 27");
+        assert_eq!(expression.evaluate().unwrap(), 3267);
+
+        let all = [Operation::Add, Operation::Mul, Operation::Concat];
+        assert_eq!(solve(7290, &[6, 8, 6, 15], &all).unwrap().to_string(), "6 * 8 || 6 * 15");
+        assert_eq!(solve(161011, &[16, 10, 13], &all), None);
+
+        // nothing divides back out of a zero, but anything times zero is still zero
+        let zero = solve(0, &[6, 6, 0], &[Operation::Add, Operation::Mul]).unwrap();
+        assert_eq!(zero.to_string(), "6 + 6 * 0");
+        assert_eq!(zero.evaluate().unwrap(), 0);
+        assert_eq!(solve(1, &[6, 0, 6], &[Operation::Add, Operation::Mul]), None);
+        assert_eq!(solve(6, &[6, 0, 6], &all).unwrap().evaluate().unwrap(), 6);
+
+        // the values before the zero still have to combine without overflowing
+        let add_mul = [Operation::Add, Operation::Mul];
+        let zero = solve(0, &[u64::MAX, 1, 0], &add_mul).unwrap();
+        assert_eq!(zero.to_string(), format!("{} * 1 * 0", u64::MAX));
+        assert_eq!(zero.evaluate().unwrap(), 0);
+        assert_eq!(solve(0, &[u64::MAX, 2, 0], &add_mul), None);
+
+        let overflowing = Expression { values: vec![u64::MAX, 2], operations: vec![Operation::Mul] };
+        assert!(overflowing.evaluate().unwrap_err().to_string().contains("overflows"));
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        use itertools::Itertools;
+
+        let all = [Operation::Add, Operation::Mul, Operation::Concat];
+
+        for length in 1..=4 {
+            for values in std::iter::repeat_n(0..4u64, length).multi_cartesian_product() {
+                let made = std::iter::repeat_n(all, length - 1)
+                    .multi_cartesian_product()
+                    .filter_map(|operations| Expression { values: values.clone(), operations }.evaluate().ok())
+                    .collect::<std::collections::HashSet<_>>();
+
+                for target in 0..=400 {
+                    let solved = solve(target, &values, &all);
+                    assert_eq!(solved.is_some(), made.contains(&target), "{target}: {values:?}");
+                    assert!(solved.is_none_or(|expression| expression.evaluate().unwrap() == target));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn y2024d07p01_examples() {
+        crate::testing::verify_examples(&Day, crate::Part::One);
+    }
+
+    #[test]
+    fn y2024d07p02_examples() {
+        crate::testing::verify_examples(&Day, crate::Part::Two);
     }
 }
@@ -1,65 +1,38 @@
 use itertools::Itertools;
-use std::{
-    collections::{HashMap, HashSet},
-    ops::{Add, Sub},
-};
+use std::collections::{HashMap, HashSet};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct Point(pub i64, pub i64);
-
-impl Point {
-    pub fn within_bounds(&self, width: i64, height: i64) -> bool {
-        0 <= self.0 && self.0 <= width && 0 <= self.1 && self.1 <= height
-    }
-}
-
-impl Add for Point {
-    type Output = Point;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        Self(self.0 + rhs.0, self.1 + rhs.1)
-    }
-}
-
-impl Sub for Point {
-    type Output = Point;
-
-    fn sub(self, rhs: Self) -> Self::Output {
-        Self(self.0 - rhs.0, self.1 - rhs.1)
-    }
-}
+use crate::{grid::Grid, math, point::Point, Answer};
 
 #[derive(Debug, Clone)]
 pub struct Input {
     pub map: HashMap<char, HashSet<Point>>,
-    pub width: i64,
-    pub height: i64,
+    pub grid: Grid<char>,
 }
 
-pub fn get_input() -> crate::Result<Input> {
-    let input = crate::get_input(2024, 8)?;
+impl Input {
+    pub fn within_bounds(&self, point: Point) -> bool {
+        self.grid.contains(point)
+    }
+}
 
+pub fn parse(input: &str) -> crate::Result<Input> {
+    let grid = Grid::parse(input.trim(), |ch| ch)?;
     let mut map: HashMap<char, HashSet<Point>> = HashMap::new();
-    let mut width = 0;
-    let mut height = 0;
-
-    for (y, line) in input.trim().lines().enumerate() {
-        for (x, char) in line.chars().enumerate() {
-            if char != '.' {
-                map.entry(char).or_default().insert(Point(x as _, y as _));
-            }
 
-            width = width.max(x);
+    for (point, &char) in grid.iter() {
+        if char != '.' {
+            map.entry(char).or_default().insert(point);
         }
-        height = height.max(y);
     }
 
-    eyre::ensure!(width == height, "grid isn't square!");
+    Ok(Input { map, grid })
+}
 
-    Ok(Input { map, width: width as _, height: height as _ })
+pub fn get_input() -> crate::Result<Input> {
+    parse(&crate::get_input(2024, 8)?)
 }
 
-pub fn part_one(input: &Input) -> u64 {
+pub fn part_one(input: &Input) -> crate::Result<Answer> {
     let mut antinodes = HashSet::<Point>::new();
 
     for points in input.map.values() {
@@ -67,63 +40,64 @@ pub fn part_one(input: &Input) -> u64 {
             let diff = a - b;
 
             for point in [a - diff, a + diff, b - diff, b + diff] {
-                if point.within_bounds(input.width, input.height) && point != a && point != b {
+                if input.within_bounds(point) && point != a && point != b {
                     antinodes.insert(point);
                 }
             }
         }
     }
 
-    antinodes.len() as _
+    Ok(antinodes.len().into())
 }
 
-pub fn part_two(input: &Input) -> u64 {
+pub fn part_two(input: &Input) -> crate::Result<Answer> {
     let mut antinodes = HashSet::new();
 
     for points in input.map.values() {
         for (a, b) in points.iter().cloned().tuple_combinations() {
+            // the smallest step between grid points on the line through both, so none are skipped
             let diff = a - b;
+            let divisor = math::gcd(diff.x.unsigned_abs(), diff.y.unsigned_abs()) as i64;
+            let step = Point::new(diff.x / divisor, diff.y / divisor);
 
-            // walk forwards from a until out of bounds
+            // walk forwards from a until out of bounds, b is on the way one direction or the other
             let mut c = a;
-            while c.within_bounds(input.width, input.height) {
+            while input.within_bounds(c) {
                 antinodes.insert(c);
-                c = c + diff;
+                c += step;
             }
 
             // walk backwards from a until out of bounds
             let mut c = a;
-            while c.within_bounds(input.width, input.height) {
+            while input.within_bounds(c) {
                 antinodes.insert(c);
-                c = c - diff;
-            }
-
-            // walk forwards from b until out of bounds
-            let mut c = b;
-            while c.within_bounds(input.width, input.height) {
-                antinodes.insert(c);
-                c = c + diff;
-            }
-
-            // walk backwards from b until out of bounds
-            let mut c = b;
-            while c.within_bounds(input.width, input.height) {
-                antinodes.insert(c);
-                c = c - diff;
+                c -= step;
             }
         }
     }
 
-    antinodes.len() as _
+    Ok(antinodes.len().into())
 }
 
-pub fn main() -> crate::Result<()> {
-    let input = get_input()?;
+pub struct Day;
+
+impl crate::Solution for Day {
+    const YEAR: u64 = 2024;
+    const DAY: u64 = 8;
+
+    type Input = Input;
 
-    tracing::info!("y2024d08p01: {}", part_one(&input));
-    tracing::info!("y2024d08p02: {}", part_two(&input));
+    fn parse(input: &str) -> crate::Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part_one(input: &Self::Input) -> crate::Result<Answer> {
+        part_one(input)
+    }
 
-    Ok(())
+    fn part_two(input: &Self::Input) -> crate::Result<Answer> {
+        part_two(input)
+    }
 }
 
 #[cfg(test)]
@@ -132,13 +106,43 @@ mod tests {
 
     #[test]
     fn y2024d08p01() {
-        let input = get_input().unwrap();
-        assert_eq!(part_one(&input), 351);
+        crate::testing::verify_answer(&Day, crate::Part::One);
     }
 
     #[test]
     fn y2024d08p02() {
-        let input = get_input().unwrap();
-        assert_eq!(part_two(&input), 1_259);
+        crate::testing::verify_answer(&Day, crate::Part::Two);
+    }
+
+    #[test]
+    fn ragged_map() {
+        let error = crate::Solver::parse(&Day, "\n..a.\n..\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 3, column 3: the line is 2 wide, expected 4 like the lines before it");
+    }
+
+    #[test]
+    fn non_square_map() {
+        // antennas at (1, 0) and (2, 1), their antinodes at (0, -1) and (3, 2) are off this short, wide map
+        let input = parse(".a....\n..a...\n").unwrap();
+        assert_eq!((input.grid.width(), input.grid.height()), (6, 2));
+        assert_eq!(part_one(&input).unwrap(), "0");
+        assert_eq!(part_two(&input).unwrap(), "2");
+    }
+
+    #[test]
+    fn antinodes_between_antennas() {
+        // (1, 1) is in line with the antennas at (0, 0) and (2, 2), right between them
+        let input = parse("a..\n...\n..a\n").unwrap();
+        assert_eq!(part_two(&input).unwrap(), "3");
+    }
+
+    #[test]
+    fn y2024d08p01_examples() {
+        crate::testing::verify_examples(&Day, crate::Part::One);
+    }
+
+    #[test]
+    fn y2024d08p02_examples() {
+        crate::testing::verify_examples(&Day, crate::Part::Two);
     }
 }
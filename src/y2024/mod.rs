@@ -7,15 +7,5 @@ pub mod d06;
 pub mod d07;
 pub mod d08;
 
-pub fn main() -> crate::Result<()> {
-    d01::main()?;
-    d02::main()?;
-    d03::main()?;
-    d04::main()?;
-    d05::main()?;
-    d06::main()?;
-    d07::main()?;
-    d08::main()?;
-
-    Ok(())
-}
+pub static SOLUTIONS: &[&dyn crate::Solver] =
+    &[&d01::Day, &d02::Day, &d03::Day, &d04::Day, &d05::Day, &d06::Day, &d07::Day, &d08::Day];